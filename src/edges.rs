@@ -0,0 +1,1067 @@
+//! Loading edge files.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use falkordb::FalkorValue;
+use log::{debug, error, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+use std::sync::atomic::Ordering;
+
+use crate::files::{CsvBatchReader, CsvRows, RawCsvRecord};
+use crate::loader::FalkorDBCSVLoader;
+use crate::metrics::{Checksum, ProgressThreshold};
+use crate::options::BatchKind;
+use crate::rejects::TARGET_NOT_FOUND;
+
+impl FalkorDBCSVLoader {
+    /// Parse the `--weight-column` value of an edge row as a float. Empty cells yield `None`,
+    /// non-numeric values are an error pointing at the source line.
+    fn parse_edge_weight(column: &str, row: &HashMap<String, String>, file_name: &str, raw: &RawCsvRecord) -> Result<Option<f64>> {
+        let value = row.get(column).map(|v| v.trim()).unwrap_or("");
+        if value.is_empty() {
+            return Ok(None);
+        }
+        match value.parse::<f64>() {
+            Ok(weight) if weight.is_finite() => Ok(Some(weight)),
+            _ => Err(anyhow!(
+                "Non-numeric weight {:?} in column '{}' of {} line {}",
+                value, column, file_name, raw.line
+            )),
+        }
+    }
+    
+    /// Remember the ids of a node file's rows in load order so edges can reference them by
+    /// ordinal. Ordinals continue across part files of the same label; rows without an id
+    /// are assigned their ordinal as id.
+    pub(crate) fn record_node_ordinals(&self, label: &str, rows: &mut [HashMap<String, String>]) {
+        let mut ids_by_label = self.node_ids_by_ordinal.lock().unwrap_or_else(|e| e.into_inner());
+        let ids = ids_by_label.entry(label.to_string()).or_default();
+        
+        for row in rows.iter_mut() {
+            let ordinal = ids.len();
+            let id = row.entry("id".to_string()).or_default();
+            if id.is_empty() {
+                *id = ordinal.to_string();
+            }
+            ids.push(id.clone());
+        }
+    }
+    
+    /// Replace ordinal `source`/`target` values of edge rows with the ids of the nodes loaded
+    /// at those positions. Unresolvable endpoints are cleared so the row is skipped; returns
+    /// how many there were.
+    pub(crate) fn resolve_edge_ordinals(&self, rows: &mut [HashMap<String, String>]) -> usize {
+        let ids_by_label = self.node_ids_by_ordinal.lock().unwrap_or_else(|e| e.into_inner());
+        let mut unresolved = 0;
+        
+        for row in rows.iter_mut() {
+            for (endpoint, label_column) in [("source", "source_label"), ("target", "target_label")] {
+                let raw_label = row.get(label_column).map(|l| l.trim()).unwrap_or("");
+                let label = self.label_mapping.get(raw_label).map_or(raw_label, |l| l.as_str());
+                let resolved = ids_by_label.get(label)
+                    .or_else(|| ids_by_label.get(self.primary_label(label)))
+                    .zip(row.get(endpoint).and_then(|v| v.trim().parse::<usize>().ok()))
+                    .and_then(|(ids, ordinal)| ids.get(ordinal).cloned());
+                
+                match resolved {
+                    Some(id) => {
+                        row.insert(endpoint.to_string(), id);
+                    }
+                    None => {
+                        unresolved += 1;
+                        row.insert(endpoint.to_string(), String::new());
+                    }
+                }
+            }
+        }
+        unresolved
+    }
+    
+    /// Whether an edge row's direction column says target -> source; `None` for an unknown value
+    pub(crate) fn is_reversed_edge(&self, row: &HashMap<String, String>, column: &str) -> Option<bool> {
+        let value = row.get(column).map_or("", |v| v.trim());
+        let matches = |values: &[String]| values.iter().any(|v| v.trim().eq_ignore_ascii_case(value));
+        if value.is_empty() || matches(&self.options.forward_values) {
+            Some(false)
+        } else if matches(&self.options.reverse_values) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+    
+    /// Apply `--direction-column`: swap the endpoints of reversed rows and move them after
+    /// the forward rows (keeping file order within each group). Rows with an unknown direction
+    /// are rejected. Returns the index of the first reversed row.
+    fn orient_edge_rows(&self, file_name: &str, column: &str, rows: &mut CsvRows, raw_records: &mut Vec<RawCsvRecord>) -> Result<usize> {
+        let mut forward = (Vec::new(), Vec::new());
+        let mut reversed = (Vec::new(), Vec::new());
+        
+        for (mut row, mut raw) in rows.drain(..).zip(raw_records.drain(..)) {
+            match self.is_reversed_edge(&row, column) {
+                Some(false) => {
+                    forward.0.push(row);
+                    forward.1.push(raw);
+                }
+                Some(true) => {
+                    for (a, b) in [("source", "target"), ("source_label", "target_label")] {
+                        let (value_a, value_b) = (row.remove(a), row.remove(b));
+                        row.extend(value_b.map(|v| (a.to_string(), v)));
+                        row.extend(value_a.map(|v| (b.to_string(), v)));
+                        let (quoted_a, quoted_b) = (raw.quoted_columns.remove(a), raw.quoted_columns.remove(b));
+                        if quoted_b {
+                            raw.quoted_columns.insert(a.to_string());
+                        }
+                        if quoted_a {
+                            raw.quoted_columns.insert(b.to_string());
+                        }
+                    }
+                    reversed.0.push(row);
+                    reversed.1.push(raw);
+                }
+                None => {
+                    let value = row.get(column).cloned().unwrap_or_default();
+                    warn!("⚠️ Unknown value {:?} in direction column '{}' of {}, row skipped", value, column, file_name);
+                    self.report_rejected_row(file_name, &raw, &format!("unknown direction {:?}", value))?;
+                }
+            }
+        }
+        
+        let reversed_start = forward.0.len();
+        rows.extend(forward.0.into_iter().chain(reversed.0));
+        raw_records.extend(forward.1.into_iter().chain(reversed.1));
+        Ok(reversed_start)
+    }
+    
+    /// Column names of the `{column}` placeholders in a `--rel-type-template`
+    fn template_columns(template: &str) -> Vec<&str> {
+        template.split('{').skip(1).filter_map(|part| part.split_once('}').map(|(column, _)| column)).collect()
+    }
+    
+    /// Template giving each edge row its relationship type, with the option it comes from:
+    /// `--rel-type-template`, or `{COLUMN}` for `--rel-type-column`
+    pub(crate) fn row_rel_type_template(&self) -> Option<(String, &'static str)> {
+        match (&self.options.rel_type_template, &self.options.rel_type_column) {
+            (Some(template), _) => Some((template.clone(), "--rel-type-template")),
+            (None, Some(column)) => Some((format!("{{{}}}", column), "--rel-type-column")),
+            (None, None) => None,
+        }
+    }
+    
+    /// Relationship type of a row under a `--rel-type-template`. Placeholder values are
+    /// sanitized to ASCII letters, digits and `_`; an empty value is an error.
+    pub(crate) fn render_rel_type(template: &str, option: &str, row: &HashMap<String, String>) -> std::result::Result<String, String> {
+        let mut rel_type = String::new();
+        let mut rest = template;
+        while let Some((before, after)) = rest.split_once('{') {
+            let (column, after) = after.split_once('}').unwrap_or((after, ""));
+            let value = row.get(column).map_or("", |v| v.trim());
+            if value.is_empty() {
+                return Err(format!("empty '{}' for {}", column, option));
+            }
+            rel_type.push_str(before);
+            rel_type.extend(value.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }));
+            rest = after;
+        }
+        rel_type.push_str(rest);
+        Ok(rel_type)
+    }
+    
+    /// Apply `--rel-type-template`: resolve each row's relationship type and group rows of the
+    /// same type together within the forward and the reversed segment, keeping file order inside
+    /// a group. Rows with an empty placeholder value are rejected. Returns the start index and
+    /// type of every group.
+    fn group_rows_by_rel_type(&self, file_name: &str, template: &str, option: &str, rows: &mut CsvRows,
+                              raw_records: &mut Vec<RawCsvRecord>, reversed_start: &mut usize) -> Result<Vec<(usize, String)>> {
+        if let Some(first) = rows.first() {
+            if let Some(column) = Self::template_columns(template).into_iter().find(|column| !first.contains_key(*column)) {
+                return Err(anyhow!("{} has no column '{}' used by {} {:?}", file_name, column, option, template));
+            }
+        }
+        
+        // Groups in order of first appearance, per segment
+        let mut segments: [Vec<(String, CsvRows, Vec<RawCsvRecord>)>; 2] = [Vec::new(), Vec::new()];
+        for (i, (row, raw)) in rows.drain(..).zip(raw_records.drain(..)).enumerate() {
+            let groups = &mut segments[usize::from(i >= *reversed_start)];
+            match Self::render_rel_type(template, option, &row) {
+                Ok(rel_type) => {
+                    let index = match groups.iter().position(|(group_type, _, _)| *group_type == rel_type) {
+                        Some(index) => index,
+                        None => {
+                            groups.push((rel_type, Vec::new(), Vec::new()));
+                            groups.len() - 1
+                        }
+                    };
+                    groups[index].1.push(row);
+                    groups[index].2.push(raw);
+                }
+                Err(reason) => {
+                    warn!("⚠️ {} line {}: {}, row skipped", file_name, raw.line, reason);
+                    self.report_rejected_row(file_name, &raw, &reason)?;
+                }
+            }
+        }
+        
+        let mut starts = Vec::new();
+        for (segment, groups) in segments.into_iter().enumerate() {
+            if segment == 1 {
+                *reversed_start = rows.len();
+            }
+            for (rel_type, group_rows, group_raw) in groups {
+                starts.push((rows.len(), rel_type));
+                rows.extend(group_rows);
+                raw_records.extend(group_raw);
+            }
+        }
+        if !starts.is_empty() {
+            info!("  {} rows in {} load as {} relationship types: {:?}", rows.len(), file_name, starts.len(),
+                  starts.iter().map(|(_, rel_type)| rel_type.as_str()).collect::<BTreeSet<_>>());
+        }
+        Ok(starts)
+    }
+    
+    /// Load edges from CSV file in batches using UNWIND for better performance
+    pub async fn load_edges_batch<P: AsRef<Path>>(&self, file_path: P, batch_size: usize) -> Result<()> {
+        let start_time = Instant::now();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] Loading edges from {:?}...", timestamp, file_path.as_ref());
+        
+        // Extract relationship type from filename
+        let filename = file_path.as_ref()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        if !self.is_edge_file(&filename) {
+            return Err(anyhow!("Not an edge file: {:?}", file_path.as_ref()));
+        }
+        // An unnamed `edges.csv` only gets types from its rows and goes by its file name
+        let rel_type = Self::entity_name_from_file_name(&filename, "edges_").unwrap_or(&filename);
+        let rel_type_ident = self.rel_type_identifier(rel_type);
+        let column_types = self.options.schema.get(rel_type);
+        
+        // Rows are read one chunk of `batch_size` at a time and only ever reordered within a
+        // chunk, so only the chunk being loaded is held in memory
+        let mut chunks = CsvBatchReader::open(file_path.as_ref(), &self.options)?;
+        let (mut rows, mut raw_records) = chunks.next_batch(batch_size)?;
+        if rows.is_empty() {
+            if self.options.fail_on_empty_file {
+                return Err(anyhow!("File {:?} has no data rows", file_path.as_ref()));
+            }
+            return Ok(());
+        }
+        
+        let (source_key, target_key) = self.endpoint_keys(rel_type);
+        let append_columns = self.append_list_columns(rows.first());
+        let positions = self.column_positions(&file_path)?;
+        
+        // The weight column is set separately so it always stays a float
+        let weight_column = self.options.weight_column.as_deref()
+            .filter(|_| !self.options.topology_only);
+        if let Some(column) = weight_column {
+            if !rows.first().is_some_and(|row| row.contains_key(column)) {
+                return Err(anyhow!("Weight column '{}' missing from edge file {}", column, filename));
+            }
+        }
+        let mut missing_weight_lines = Vec::new();
+        
+        // Relationship clause used in merge mode
+        let rel_clause = if self.options.no_merge_relationships { "CREATE" } else { "MERGE" };
+        let has_vectors = self.has_vector_columns(rows.first());
+        
+        // Extra SET clauses for append-list, weight and vector columns and the source stamp
+        let mut extra_set = Self::append_list_set_clause("r", &append_columns);
+        if let Some(column) = weight_column {
+            extra_set.push_str(&format!(", r.{c} = coalesce(row.weight, r.{c})", c = column));
+        }
+        if has_vectors {
+            extra_set.push_str(", r += row.vectors");
+        }
+        let source_stamp = self.source_stamp(&filename);
+        if let Some((property, value)) = &source_stamp {
+            extra_set.push_str(&format!(", r.{} = {}", property, value));
+        }
+        
+        let mut total_loaded = 0;
+        // Progress needs the row count up front, which takes a separate pass over the file
+        let total_records = if self.options.progress_bars.is_some() || self.progress_interval > 0 {
+            self.file_record_count(file_path.as_ref())
+        } else {
+            0
+        };
+        let file_bar = self.progress_bar(&filename, total_records);
+        let mut progress = ProgressThreshold::new(self.progress_interval);
+        let mut file_stats = self.options.column_stats.then(BTreeMap::new);
+        let mut file_checksum = self.options.checksum.then(Checksum::default);
+        // Endpoint ids per label and key property, kept only when unmatched endpoints are reported
+        let mut endpoint_ids: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+        let mut empty_endpoint_rows = 0;
+        let mut missing_endpoint_rows = 0;
+        let mut consecutive_empty_batches = 0;
+        let mut rows_read = 0;
+        let mut reversed_rows = 0;
+        let mut unresolved_ordinals = 0;
+        let mut indexed_endpoint_keys = BTreeSet::new();
+        let mut next_batch_num = 0;
+        
+        while !rows.is_empty() {
+            rows_read += rows.len();
+            if self.options.edges_by_ordinal {
+                unresolved_ordinals += self.resolve_edge_ordinals(&mut rows);
+            }
+            self.drop_oversized_rows(&filename, rel_type, &mut rows, &mut raw_records)?;
+            
+            // Reversed rows come after forward ones and batches never straddle the two groups,
+            // so each batch keeps using its first row's endpoint labels
+            let mut reversed_start = match &self.options.direction_column {
+                Some(column) => self.orient_edge_rows(&filename, column, &mut rows, &mut raw_records)?,
+                None => rows.len(),
+            };
+            reversed_rows += rows.len() - reversed_start;
+            
+            // Likewise batches never straddle two relationship types taken from the rows
+            let type_groups = match self.row_rel_type_template() {
+                Some((template, option)) => self.group_rows_by_rel_type(&filename, &template, option, &mut rows, &mut raw_records, &mut reversed_start)?,
+                None => Vec::new(),
+            };
+            
+            if (source_key, target_key) != ("id", "id") {
+                self.check_endpoint_key_indexes(&filename, &rows, &raw_records, reversed_start, (source_key, target_key),
+                                                &mut indexed_endpoint_keys).await?;
+            }
+            
+            // Process in batches
+            let mut splits: Vec<usize> = type_groups.iter().map(|(start, _)| *start).collect();
+            splits.push(reversed_start);
+            for range in Self::batch_ranges(rows.len(), batch_size, &splits) {
+                let batch_num = next_batch_num;
+                next_batch_num += 1;
+                self.pace_throughput(range.len()).await;
+                let batch_start_time = Instant::now();
+                let batch_start = range.start;
+                let batch = &rows[range];
+                let rel_type_ident = match type_groups.iter().rev().find(|(start, _)| *start <= batch_start) {
+                    Some((_, batch_rel_type)) => self.rel_type_identifier(batch_rel_type),
+                    None => rel_type_ident.clone(),
+                };
+                // Reversed rows had their endpoints swapped, so the key properties swap with them
+                let (batch_source_key, batch_target_key) = if batch_start >= reversed_start {
+                    (target_key, source_key)
+                } else {
+                    (source_key, target_key)
+                };
+                
+                // Check if we should terminate before processing batch
+                if self.terminate_on_error.load(Ordering::Relaxed) {
+                    return Err(anyhow!("Loading terminated due to previous critical errors"));
+                }
+                self.check_memory_limit(&filename, rows.len())?;
+                
+                let missing_endpoints = if self.options.verify_endpoints {
+                    self.missing_edge_endpoints(batch, &raw_records[batch_start..batch_start + batch.len()],
+                                                batch_source_key, batch_target_key).await?
+                } else {
+                    BTreeMap::new()
+                };
+                
+                // Build batch data as Cypher list literals
+                let mut batch_items = Vec::new();
+                let mut hook_rows = Vec::new();
+                let mut first_source_label = String::new();
+                let mut first_target_label = String::new();
+                
+                for (j, row) in batch.iter().enumerate() {
+                    let empty_string = String::new();
+                    let source_id = row.get("source").unwrap_or(&empty_string);
+                    let target_id = row.get("target").unwrap_or(&empty_string);
+                    if let Some(stats) = &mut file_stats {
+                        self.observe_column_stats(stats, row, &raw_records[batch_start + j]);
+                    }
+                    
+                    if source_id.is_empty() || target_id.is_empty() {
+                        empty_endpoint_rows += 1;
+                        continue;
+                    }
+                    
+                    let mut properties = HashMap::new();
+                    let mut appended = HashMap::new();
+                    
+                    // Get source and target labels if available
+                    let raw_source_label = row.get("source_label").unwrap_or(&empty_string).trim();
+                    let raw_target_label = row.get("target_label").unwrap_or(&empty_string).trim();
+                    
+                    // Apply label mapping to resolve case mismatches
+                    let source_label = self.label_mapping.get(raw_source_label)
+                        .map_or(raw_source_label, |s| s.as_str());
+                    let target_label = self.label_mapping.get(raw_target_label)
+                        .map_or(raw_target_label, |s| s.as_str());
+                    
+                    // Get first label for nodes (handle multiple labels)
+                    let source_label_first = self.primary_label(source_label);
+                    let target_label_first = self.primary_label(target_label);
+                    
+                    // Store first labels for query construction
+                    if j == 0 && !source_label_first.is_empty() && !target_label_first.is_empty() {
+                        first_source_label = self.label_identifier(source_label_first);
+                        first_target_label = self.label_identifier(target_label_first);
+                    }
+                    
+                    let raw = &raw_records[batch_start + j];
+                    if let Some(endpoints) = missing_endpoints.get(&j) {
+                        let reason = format!("no node for {}", endpoints.join(" and "));
+                        debug!("  {} line {}: {}, row skipped", filename, raw.line, reason);
+                        self.write_dead_letter(&filename, raw, &reason)?;
+                        missing_endpoint_rows += 1;
+                        continue;
+                    }
+                    let mut string_keys = HashSet::new();
+                    
+                    let weight = match weight_column {
+                        Some(column) => {
+                            let weight = Self::parse_edge_weight(column, row, &filename, raw)?;
+                            if weight.is_none() {
+                                missing_weight_lines.push(raw.line);
+                            }
+                            weight
+                        }
+                        None => None,
+                    };
+                    
+                    if self.options.unmatched_edges_output.is_some() {
+                        endpoint_ids.entry((source_label_first.to_string(), batch_source_key.to_string())).or_default().insert(source_id.clone());
+                        endpoint_ids.entry((target_label_first.to_string(), batch_target_key.to_string())).or_default().insert(target_id.clone());
+                    }
+                    
+                    // Add all properties except source, target, type, source_label, target_label
+                    // (none in topology-only mode)
+                    for (key, value) in Self::ordered_entries(row, &positions) {
+                        if !self.options.topology_only
+                           && !["source", "target", "type", "source_label", "target_label"].contains(&key.as_str())
+                           && weight_column != Some(key.as_str())
+                           && self.options.direction_column.as_ref() != Some(key)
+                           && self.options.rel_type_column.as_ref() != Some(key)
+                           && !self.options.vector_columns.contains_key(key)
+                           && !value.is_empty() {
+                            // Clean up property key: remove duplicate prefixes like 'Date:Date' -> 'Date'
+                            let clean_key = if key.contains(':') {
+                                let parts: Vec<&str> = key.split(':').collect();
+                                if parts.len() == 2 && parts[0] == parts[1] {
+                                    parts[0].to_string()
+                                } else {
+                                    key.clone()
+                                }
+                            } else {
+                                key.clone()
+                            };
+                            
+                            let quoted = raw.quoted_columns.contains(key);
+                            let Some(value) = Self::property_value(value, quoted, &self.options) else {
+                                continue;
+                            };
+                            if quoted {
+                                string_keys.insert(clean_key.clone());
+                            }
+                            if append_columns.contains(key) {
+                                appended.insert(clean_key, value);
+                            } else if !self.is_omitted_default(&clean_key, &value, quoted) {
+                                properties.insert(clean_key, value);
+                            }
+                        }
+                    }
+                    
+                    // Debug: show label usage for first few records
+                    if batch_num == 0 && j < 3 {
+                        info!("    Record {}: raw_source_label='{}' -> '{}', raw_target_label='{}' -> '{}'", 
+                              j + 1, raw_source_label, source_label_first, raw_target_label, target_label_first);
+                    }
+                    
+                    // Build Cypher map: {source_id: val, target_id: val, props: {...}}
+                    let source_id_literal = Self::row_id_literal(row, raw, "source");
+                    let target_id_literal = Self::row_id_literal(row, raw, "target");
+                    let props_map = Self::build_cypher_map(&properties, &string_keys, &positions, &self.options, column_types);
+                    let mut item = format!(
+                        "{{source_id: {}, target_id: {}, props: {}",
+                        source_id_literal, target_id_literal, props_map
+                    );
+                    if !append_columns.is_empty() {
+                        item.push_str(&format!(", append: {}", Self::build_cypher_map(&appended, &string_keys, &positions, &self.options, column_types)));
+                    }
+                    if let Some(weight) = weight {
+                        item.push_str(&format!(", weight: {}", Self::float_literal(weight)));
+                    }
+                    if has_vectors {
+                        item.push_str(&format!(", vectors: {{{}}}", self.vector_entries(row, &filename, raw)?.join(", ")));
+                    }
+                    item.push('}');
+                    
+                    if let Some(checksum) = &mut file_checksum {
+                        let ids = [
+                            Self::canonical_csv_value(source_id, raw.quoted_columns.contains("source")),
+                            Self::canonical_csv_value(target_id, raw.quoted_columns.contains("target")),
+                        ];
+                        checksum.add(&Self::entity_fingerprint(&ids, &properties, &string_keys, &self.options));
+                    }
+                    
+                    if self.options.batch_hook.is_some() {
+                        let mut hook_row = serde_json::Map::new();
+                        hook_row.insert("source_id".to_string(), Self::typed_json_value(source_id, raw.quoted_columns.contains("source")));
+                        hook_row.insert("target_id".to_string(), Self::typed_json_value(target_id, raw.quoted_columns.contains("target")));
+                        hook_row.insert("props".to_string(), Self::json_map(&properties, &string_keys, &self.options, column_types));
+                        if !append_columns.is_empty() {
+                            hook_row.insert("append".to_string(), Self::json_map(&appended, &string_keys, &self.options, column_types));
+                        }
+                        if let Some(weight) = weight {
+                            hook_row.insert("weight".to_string(), serde_json::json!(weight));
+                        }
+                        if has_vectors {
+                            let vectors: serde_json::Map<_, _> = self.vector_values(row, &filename, raw)?.into_iter()
+                                .map(|(column, elements)| (column, serde_json::json!(elements)))
+                                .collect();
+                            hook_row.insert("vectors".to_string(), vectors.into());
+                        }
+                        hook_rows.push(serde_json::Value::Object(hook_row));
+                    }
+                    batch_items.push(item);
+                }
+                
+                if let Some(hook) = &self.options.batch_hook {
+                    batch_items = Self::apply_batch_hook(hook, BatchKind::Edges(rel_type), hook_rows);
+                }
+                if batch_items.is_empty() {
+                    consecutive_empty_batches += 1;
+                    // A wrong column name filters out every row, which would otherwise "succeed" silently
+                    if self.options.max_empty_batches.is_some_and(|max| consecutive_empty_batches >= max) {
+                        return Err(anyhow!(
+                            "{} consecutive batches of {} had no rows to load after filtering ({} rows so far had an empty source or target); check the column names",
+                            consecutive_empty_batches, filename, empty_endpoint_rows
+                        ));
+                    }
+                    continue;
+                }
+                consecutive_empty_batches = 0;
+                
+                // Build complete UNWIND query with inline batch data
+                let batch_literal = format!("[{}]", batch_items.join(", "));
+                
+                // Create single UNWIND query for the entire batch
+                // Use the first label from multi-labels for efficient index usage
+                let mut unwind_query = if self.options.properties_only {
+                    // Only update existing relationships; returned endpoints tell us which rows matched
+                    if !first_source_label.is_empty() && !first_target_label.is_empty() {
+                        format!(
+                            "UNWIND {} AS row \
+                             MATCH (a:{} {{{batch_source_key}: row.source_id}})-[r:{}]->(b:{} {{{batch_target_key}: row.target_id}}) \
+                             SET r += row.props{} \
+                             RETURN row.source_id, row.target_id",
+                            batch_literal, first_source_label, rel_type_ident, first_target_label, extra_set
+                        )
+                    } else {
+                        format!(
+                            "UNWIND {} AS row \
+                             MATCH (a {{{batch_source_key}: row.source_id}})-[r:{}]->(b {{{batch_target_key}: row.target_id}}) \
+                             SET r += row.props{} \
+                             RETURN row.source_id, row.target_id",
+                            batch_literal, rel_type_ident, extra_set
+                        )
+                    }
+                } else if self.merge_mode {
+                    if !first_source_label.is_empty() && !first_target_label.is_empty() {
+                        format!(
+                            "UNWIND {} AS row \
+                             MERGE (a:{} {{{batch_source_key}: row.source_id}}) \
+                             MERGE (b:{} {{{batch_target_key}: row.target_id}}) \
+                             {} (a)-[r:{}]->(b) \
+                             SET r += row.props{}",
+                            batch_literal, first_source_label, first_target_label, rel_clause, rel_type_ident, extra_set
+                        )
+                    } else {
+                        format!(
+                            "UNWIND {} AS row \
+                             MERGE (a {{{batch_source_key}: row.source_id}}) \
+                             MERGE (b {{{batch_target_key}: row.target_id}}) \
+                             {} (a)-[r:{}]->(b) \
+                             SET r += row.props{}",
+                            batch_literal, rel_clause, rel_type_ident, extra_set
+                        )
+                    }
+                } else if !first_source_label.is_empty() && !first_target_label.is_empty() {
+                    format!(
+                        "UNWIND {} AS row \
+                         MATCH (a:{} {{{batch_source_key}: row.source_id}}) \
+                         MATCH (b:{} {{{batch_target_key}: row.target_id}}) \
+                         CREATE (a)-[r:{}]->(b) \
+                         SET r += row.props{}",
+                        batch_literal, first_source_label, first_target_label, rel_type_ident, extra_set
+                    )
+                } else {
+                    format!(
+                        "UNWIND {} AS row \
+                         MATCH (a {{{batch_source_key}: row.source_id}}) \
+                         MATCH (b {{{batch_target_key}: row.target_id}}) \
+                         CREATE (a)-[r:{}]->(b) \
+                         SET r += row.props{}",
+                        batch_literal, rel_type_ident, extra_set
+                    )
+                };
+                
+                // Debug: show generated query for first batch
+                if batch_num == 0 {
+                    info!("    Generated UNWIND query template");
+                    info!("    Batch size: {} edges", batch_items.len());
+                    if !batch_items.is_empty() {
+                        info!("    First item example: {}", batch_items[0]);
+                    }
+                    if self.merge_mode {
+                        info!("    Using MERGE mode for relationships");
+                    } else {
+                        info!("    Using CREATE mode for relationships");
+                    }
+                }
+                
+                // Skip batches already committed by an earlier attempt and mark this one atomically.
+                // A batch whose fallback was interrupted resumes the fallback instead.
+                let markers = if self.idempotent_batches_enabled() {
+                    Some(self.batch_markers(Self::batch_key(&filename, batch_num, batch_size)).await?)
+                } else {
+                    None
+                };
+                if let Some(markers) = &markers {
+                    if markers.committed {
+                        info!("⏭️ Batch {} of {} already committed, skipping", batch_num + 1, filename);
+                        total_loaded += batch_items.len();
+                        continue;
+                    }
+                    if markers.loaded_rows.is_empty() {
+                        unwind_query.push_str(&Self::batch_marker_clause(&markers.key));
+                    }
+                }
+                let batch_key = markers.as_ref().map(|markers| markers.key.as_str());
+                let loaded_rows = markers.as_ref().map(|markers| &markers.loaded_rows).filter(|rows| !rows.is_empty());
+                if self.options.dry_run {
+                    self.log_dry_run_query(&self.graph_name, &unwind_query);
+                    total_loaded += batch_items.len();
+                    continue;
+                }
+                
+                // Execute UNWIND query with inline batch data, retrying connection errors. Some rows
+                // of a resumed fallback are in already, so the batch query would create them again.
+                let batch_result = match loaded_rows {
+                    Some(rows) => {
+                        info!("⏭️ Resuming the fallback of batch {} of {}, {} rows already loaded", batch_num + 1, filename, rows.len());
+                        Err(None)
+                    }
+                    None => self.send_query(false, &self.graph_name, &unwind_query, batch_key).await.map_err(Some),
+                };
+                match batch_result {
+                    Ok(result) => {
+                        self.record_query_success();
+                        if self.options.properties_only {
+                            // Endpoint ids are compared as the literals they were sent as
+                            let matched: HashSet<(String, String)> = result.into_iter()
+                                .filter_map(|row| match (row.first(), row.get(1)) {
+                                    (Some(source), Some(target)) => Some((
+                                        Self::falkor_value_literal(source),
+                                        Self::falkor_value_literal(target),
+                                    )),
+                                    _ => None,
+                                })
+                                .collect();
+                            let mut missing = Vec::new();
+                            for (j, row) in batch.iter().enumerate() {
+                                let (Some(source), Some(target)) = (row.get("source"), row.get("target")) else {
+                                    continue;
+                                };
+                                // Rows left out of the batch query were already counted or reported
+                                if source.is_empty() || target.is_empty() || missing_endpoints.contains_key(&j) {
+                                    continue;
+                                }
+                                let raw = &raw_records[batch_start + j];
+                                let sent = (
+                                    Self::typed_cypher_literal(source, raw.quoted_columns.contains("source")),
+                                    Self::typed_cypher_literal(target, raw.quoted_columns.contains("target")),
+                                );
+                                if !matched.contains(&sent) {
+                                    self.report_rejected_row(&filename, raw, TARGET_NOT_FOUND)?;
+                                    self.write_dead_letter(&filename, raw, TARGET_NOT_FOUND)?;
+                                    missing.push((source.as_str(), target.as_str()));
+                                }
+                            }
+                            if !missing.is_empty() {
+                                warn!("⚠️ {} {} relationships not found for property update, rejected (source, target): {:?}",
+                                      missing.len(), rel_type, missing);
+                            }
+                            total_loaded += batch_items.len() - missing.len();
+                        } else {
+                            total_loaded += batch_items.len();
+                        }
+                        
+                        // Report progress for batch
+                        if self.progress_interval > 0 && file_bar.is_none()
+                           && (progress.crossed(total_loaded) || total_loaded == total_records) {
+                            let percent = (total_loaded as f64 / total_records as f64) * 100.0;
+                            info!("📊 Progress: {:.1}% ({}/{}) {} edges loaded", 
+                                  percent, total_loaded, total_records, rel_type);
+                        }
+                    }
+                    Err(e) => {
+                        if let Some(e) = e {
+                            error!("❌ Error loading batch with UNWIND: {}", e);
+                            
+                            // FalkorDB rolls back every write of a query that fails on the server, so a
+                            // batch never commits partially. Only a client-side failure (timeout, dropped
+                            // connection) can hide a batch that fully committed, which the marker reveals.
+                            if let Some(key) = batch_key {
+                                if self.batch_committed(key).await.unwrap_or(false) {
+                                    warn!("⚠️ Batch {} of {} committed despite the error, not retrying it", batch_num + 1, filename);
+                                    total_loaded += batch_items.len();
+                                    continue;
+                                }
+                            }
+                            if self.options.fail_fast {
+                                self.terminate_on_error.store(true, Ordering::Relaxed);
+                                return Err(anyhow!("Batch {} of {} failed (--fail-fast): {}", batch_num + 1, filename, e));
+                            }
+                            self.check_batch_connection_error(&e, batch_num, &filename)?;
+                            self.record_query_failure(&filename)?;
+                            
+                            error!("Falling back to individual queries for this batch...");
+                        }
+                        
+                        // Fallback to individual queries if batch fails
+                        let mut successful_edges = 0;
+                        for (i, row) in batch.iter().enumerate() {
+                            let raw = &raw_records[batch_start + i];
+                            let empty_string = String::new();
+                            let source_id = row.get("source").unwrap_or(&empty_string);
+                            let target_id = row.get("target").unwrap_or(&empty_string);
+                            
+                            if source_id.is_empty() || target_id.is_empty() || missing_endpoints.contains_key(&i) {
+                                continue;
+                            }
+                            if loaded_rows.is_some_and(|rows| rows.contains(&i)) {
+                                successful_edges += 1;
+                                continue;
+                            }
+                            
+                            let mut properties = Vec::new();
+                            let raw_source_label = row.get("source_label").unwrap_or(&empty_string).trim();
+                            let raw_target_label = row.get("target_label").unwrap_or(&empty_string).trim();
+                            
+                            let source_label = self.label_mapping.get(raw_source_label)
+                                .map_or(raw_source_label, |s| s.as_str());
+                            let target_label = self.label_mapping.get(raw_target_label)
+                                .map_or(raw_target_label, |s| s.as_str());
+                            
+                            if let Some(column) = weight_column {
+                                if let Some(weight) = Self::parse_edge_weight(column, row, &filename, raw)? {
+                                    properties.push(format!("{}: {}", column, Self::float_literal(weight)));
+                                }
+                            }
+                            properties.extend(self.vector_entries(row, &filename, raw)?);
+                            properties.extend(source_stamp.iter().map(|(property, value)| format!("{}: {}", property, value)));
+                            let (column_properties, append_updates) = self.fallback_properties("r", row, raw, &positions, column_types, |key| {
+                                ["source", "target", "type", "source_label", "target_label"].contains(&key)
+                                    || weight_column == Some(key)
+                                    || self.options.direction_column.as_deref() == Some(key)
+                                    || self.options.rel_type_column.as_deref() == Some(key)
+                            });
+                            properties.extend(column_properties);
+                            
+                            let source_id_str = Self::row_id_literal(row, raw, "source");
+                            let target_id_str = Self::row_id_literal(row, raw, "target");
+                            
+                            // Get first label from multi-labels for efficient matching
+                            let source_label_first = self.primary_label(source_label);
+                            let target_label_first = self.primary_label(target_label);
+                            let source_label_ident = self.label_identifier(source_label_first);
+                            let target_label_ident = self.label_identifier(target_label_first);
+                            
+                            // Use labels if available for efficient index usage
+                            let mut edge_query = if self.options.properties_only {
+                                if !source_label_first.is_empty() && !target_label_first.is_empty() {
+                                    format!("MATCH (a:{} {{{batch_source_key}: {}}})-[r:{}]->(b:{} {{{batch_target_key}: {}}}) SET r += {{{}}} RETURN a.id",
+                                            source_label_ident, source_id_str, rel_type_ident, target_label_ident, target_id_str,
+                                            properties.join(", "))
+                                } else {
+                                    format!("MATCH (a {{{batch_source_key}: {}}})-[r:{}]->(b {{{batch_target_key}: {}}}) SET r += {{{}}} RETURN a.id",
+                                            source_id_str, rel_type_ident, target_id_str, properties.join(", "))
+                                }
+                            } else if self.merge_mode {
+                                let prop_set = if properties.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(" SET {}", properties.iter()
+                                            .map(|p| format!("r.{}", p))
+                                            .collect::<Vec<_>>()
+                                            .join(", "))
+                                };
+                                if !source_label_first.is_empty() && !target_label_first.is_empty() {
+                                    format!("MERGE (a:{} {{{batch_source_key}: {}}}) MERGE (b:{} {{{batch_target_key}: {}}}) {} (a)-[r:{}]->(b){}",
+                                            source_label_ident, source_id_str, target_label_ident, target_id_str, rel_clause, rel_type_ident, prop_set)
+                                } else {
+                                    format!("MERGE (a {{{batch_source_key}: {}}}) MERGE (b {{{batch_target_key}: {}}}) {} (a)-[r:{}]->(b){}",
+                                            source_id_str, target_id_str, rel_clause, rel_type_ident, prop_set)
+                                }
+                            } else {
+                                let prop_str = if properties.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(" {{{}}}", properties.join(", "))
+                                };
+                                if !source_label_first.is_empty() && !target_label_first.is_empty() {
+                                    format!("MATCH (a:{} {{{batch_source_key}: {}}}), (b:{} {{{batch_target_key}: {}}}) CREATE (a)-[:{}{}]->(b)",
+                                            source_label_ident, source_id_str, target_label_ident, target_id_str, rel_type_ident, prop_str)
+                                } else {
+                                    format!("MATCH (a {{{batch_source_key}: {}}}), (b {{{batch_target_key}: {}}}) CREATE (a)-[:{}{}]->(b)",
+                                            source_id_str, target_id_str, rel_type_ident, prop_str)
+                                }
+                            };
+                            // Each row records its own marker, so a rerun skips just the rows that made it
+                            if let Some(key) = batch_key {
+                                edge_query.push_str(&Self::batch_marker_clause(&Self::row_marker_key(key, i)));
+                            }
+                            
+                            match self.execute_graph_query_count(&edge_query).await {
+                                Ok(0) if self.options.properties_only => {
+                                    warn!("⚠️ {} relationship {:?} -> {:?} not found for property update, rejected",
+                                          rel_type, source_id, target_id);
+                                    self.report_rejected_row(&filename, raw, TARGET_NOT_FOUND)?;
+                                    self.write_dead_letter(&filename, raw, TARGET_NOT_FOUND)?;
+                                }
+                                Ok(_) if !append_updates.is_empty() => {
+                                    let append_query = if !source_label_first.is_empty() && !target_label_first.is_empty() {
+                                        format!("MATCH (a:{} {{{batch_source_key}: {}}})-[r:{}]->(b:{} {{{batch_target_key}: {}}}) SET {}",
+                                                source_label_ident, source_id_str, rel_type_ident, target_label_ident, target_id_str,
+                                                append_updates.join(", "))
+                                    } else {
+                                        format!("MATCH (a {{{batch_source_key}: {}}})-[r:{}]->(b {{{batch_target_key}: {}}}) SET {}",
+                                                source_id_str, rel_type_ident, target_id_str, append_updates.join(", "))
+                                    };
+                                    match self.execute_graph_query(&append_query).await {
+                                        Ok(_) => {
+                                            successful_edges += 1;
+                                            self.record_query_success();
+                                        }
+                                        Err(e2) => {
+                                            error!("❌ Error appending list properties: {}", e2);
+                                            error!("Query: {}", append_query);
+                                            self.report_rejected_row(&filename, raw, &e2.to_string())?;
+                                            self.write_dead_letter(&filename, raw, &e2.to_string())?;
+                                            self.record_query_failure(&filename)?;
+                                        }
+                                    }
+                                }
+                                Ok(_) => {
+                                    successful_edges += 1;
+                                    self.record_query_success();
+                                }
+                                Err(e2) => {
+                                    error!("❌ Error loading edge: {}", e2);
+                                    error!("Query: {}", edge_query);
+                                    self.report_rejected_row(&filename, raw, &e2.to_string())?;
+                                    self.write_dead_letter(&filename, raw, &e2.to_string())?;
+                                    self.record_query_failure(&filename)?;
+                                }
+                            }
+                        }
+                        
+                        total_loaded += successful_edges;
+                        if successful_edges != batch.len() {
+                            warn!("⚠️ Loaded {} out of {} edges in this batch", successful_edges, batch.len());
+                        }
+                    }
+                }
+                
+                let batch_duration = batch_start_time.elapsed();
+                let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+                if let Some(bar) = &file_bar {
+                    bar.inc(batch.len() as u64);
+                    if let Some(overall) = &self.overall_bar {
+                        overall.inc(batch.len() as u64);
+                    }
+                } else {
+                    info!("[{}] Batch complete: Loaded {} edges (Duration: {:?})", 
+                          timestamp, batch_items.len(), batch_duration);
+                }
+                self.record_batch_timing(&filename, batch_num, batch_items.len(), batch_duration);
+            }
+            
+            (rows, raw_records) = chunks.next_batch(batch_size)?;
+        }
+        
+        if let Some(bar) = file_bar {
+            bar.finish_and_clear();
+        }
+        if reversed_rows > 0 {
+            info!("  {} of {} rows in {} were loaded in reverse direction", reversed_rows, rows_read, filename);
+        }
+        if unresolved_ordinals > 0 {
+            warn!("⚠️ {} endpoints in {} could not be resolved as node ordinals (missing label or out of range); those rows were skipped",
+                  unresolved_ordinals, filename);
+        }
+        if let Some(stats) = file_stats {
+            self.merge_column_stats(rel_type, stats);
+        }
+        if let Some(checksum) = file_checksum {
+            self.checksums.lock().unwrap_or_else(|e| e.into_inner())
+                .entry((true, rel_type.to_string())).or_default().merge(&checksum);
+        }
+        self.close_dead_letters()?;
+        self.metrics.rows_loaded.fetch_add(total_loaded, Ordering::Relaxed);
+        let duration = start_time.elapsed();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] ✅ Loaded {} {} relationships (Duration: {:?})", 
+              timestamp, total_loaded, rel_type, duration);
+        if empty_endpoint_rows > 0 {
+            warn!("⚠️ Skipped {} of {} rows in {} with an empty source or target",
+                  empty_endpoint_rows, rows_read, filename);
+        }
+        
+        if missing_endpoint_rows > 0 {
+            warn!("⚠️ Skipped {} of {} rows in {} whose source or target node doesn't exist",
+                  missing_endpoint_rows, rows_read, filename);
+            self.metrics.missing_endpoints.fetch_add(missing_endpoint_rows, Ordering::Relaxed);
+        }
+        
+        if !missing_weight_lines.is_empty() {
+            warn!("⚠️ {} {} rows have no '{}' weight (lines {:?})",
+                  missing_weight_lines.len(), rel_type,
+                  weight_column.unwrap_or_default(), missing_weight_lines);
+        }
+        
+        if let Some(output_path) = &self.options.unmatched_edges_output {
+            self.report_unmatched_endpoints(&filename, &endpoint_ids, output_path, batch_size).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// `--verify-endpoints`: rows of an edge batch whose source or target has no node, by
+    /// position in the batch, with which endpoints are missing. Endpoints are looked up with
+    /// the first row's labels, like the batch query matches them.
+    async fn missing_edge_endpoints(&self, batch: &[HashMap<String, String>], raw_records: &[RawCsvRecord],
+                                    source_key: &str, target_key: &str) -> Result<BTreeMap<usize, Vec<String>>> {
+        let first_label = |column: &str| {
+            let raw_label = batch.first().and_then(|row| row.get(column)).map_or("", |l| l.trim());
+            let label = self.label_mapping.get(raw_label).map_or(raw_label, |l| l.as_str());
+            self.primary_label(label).to_string()
+        };
+        let (source_label, target_label) = match (first_label("source_label"), first_label("target_label")) {
+            (source, target) if !source.is_empty() && !target.is_empty() => (source, target),
+            _ => (String::new(), String::new()),
+        };
+        
+        let mut missing: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (column, label, key) in [("source", source_label, source_key), ("target", target_label, target_key)] {
+            // Each id travels with its row position, so the result doesn't depend on how ids print
+            let pairs: Vec<String> = batch.iter().enumerate()
+                .filter_map(|(j, row)| row.get(column).filter(|id| !id.is_empty()).map(|id| {
+                    format!("[{}, {}]", j, Self::typed_cypher_literal(id, raw_records[j].quoted_columns.contains(column)))
+                }))
+                .collect();
+            if pairs.is_empty() {
+                continue;
+            }
+            let node_pattern = if label.is_empty() {
+                format!("(n {{{}: pair[1]}})", key)
+            } else {
+                format!("(n:{} {{{}: pair[1]}})", self.label_identifier(&label), key)
+            };
+            let query = format!(
+                "UNWIND [{}] AS pair OPTIONAL MATCH {} WITH pair, n WHERE n IS NULL RETURN pair[0]",
+                pairs.join(", "), node_pattern
+            );
+            for row in self.execute_graph_query(&query).await? {
+                if let Some(FalkorValue::I64(j)) = row.first() {
+                    let id = batch.get(*j as usize).and_then(|row| row.get(column)).map_or("", |id| id.as_str());
+                    missing.entry(*j as usize).or_default().push(format!("{} {:?}", column, id));
+                }
+            }
+        }
+        Ok(missing)
+    }
+    
+    /// Find endpoint ids referenced by an edge file that have no matching node and append them
+    /// to the unmatched-edges output file as `file,label,id` rows
+    async fn report_unmatched_endpoints(
+        &self,
+        edge_file: &str,
+        endpoint_ids: &BTreeMap<(String, String), BTreeSet<String>>,
+        output_path: &Path,
+        batch_size: usize,
+    ) -> Result<()> {
+        let mut unmatched = Vec::new();
+        
+        for ((label, key), ids) in endpoint_ids {
+            let ids: Vec<&String> = ids.iter().collect();
+            for chunk in ids.chunks(batch_size.max(1)) {
+                let id_list = chunk.iter()
+                    .map(|id| Self::value_to_cypher_literal(id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let node_pattern = if label.is_empty() {
+                    format!("(n {{{}: id}})", key)
+                } else {
+                    format!("(n:{} {{{}: id}})", self.label_identifier(label), key)
+                };
+                let query = format!(
+                    "UNWIND [{}] AS id OPTIONAL MATCH {} WITH id, n WHERE n IS NULL RETURN id",
+                    id_list, node_pattern
+                );
+                
+                for row in self.execute_graph_query(&query).await? {
+                    if let Some(id) = row.first() {
+                        unmatched.push((label.clone(), Self::falkor_value_to_string(id)));
+                    }
+                }
+            }
+        }
+        
+        if unmatched.is_empty() {
+            info!("✓ All endpoints in {} matched existing nodes", edge_file);
+            return Ok(());
+        }
+        
+        warn!("⚠️ {} endpoint ids in {} have no matching node, writing them to {:?}",
+              unmatched.len(), edge_file, output_path);
+        let file = std::fs::OpenOptions::new().append(true).create(true).open(output_path)?;
+        let mut writer = csv::Writer::from_writer(file);
+        for (label, id) in &unmatched {
+            writer.write_record([edge_file, label.as_str(), id.as_str()])?;
+        }
+        writer.flush()?;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::BatchHook;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    
+    #[test]
+    fn edge_weight_is_a_float_in_batch_and_fallback() {
+        let raw = RawCsvRecord { line: 2, record: csv::StringRecord::new(), quoted_columns: HashSet::new() };
+        let row = HashMap::from([("w".to_string(), "3".to_string())]);
+        let weight = FalkorDBCSVLoader::parse_edge_weight("w", &row, "edges_R.csv", &raw).unwrap().unwrap();
+        
+        // The batch item and the per-row fallback both render the weight with float_literal
+        assert_eq!(FalkorDBCSVLoader::float_literal(weight), "3.0");
+        assert_eq!(FalkorDBCSVLoader::float_literal(2.5), "2.5");
+        assert_eq!(FalkorDBCSVLoader::float_literal(-1e3), "-1000.0");
+        
+        // A batch hook that writes an integer weight still sends a float
+        let hook = BatchHook(Arc::new(|_, rows: &mut Vec<serde_json::Value>| rows[0]["weight"] = serde_json::json!(3)));
+        let rows = vec![serde_json::json!({"source_id": 1, "target_id": 2, "weight": weight})];
+        let items = FalkorDBCSVLoader::apply_batch_hook(&hook, BatchKind::Edges("R"), rows);
+        assert_eq!(items, vec!["{source_id: 1, target_id: 2, weight: 3.0}".to_string()]);
+        
+        let row = HashMap::from([("w".to_string(), "heavy".to_string())]);
+        assert!(FalkorDBCSVLoader::parse_edge_weight("w", &row, "edges_R.csv", &raw).is_err());
+    }
+}
@@ -0,0 +1,477 @@
+//! Finding and reading CSV files in a directory or a tar archive.
+
+use anyhow::{anyhow, Result};
+use csv::{Reader, StringRecord};
+use log::info;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::loader::FalkorDBCSVLoader;
+use crate::options::{ColumnType, FileOrder, LoaderOptions, TrimValues};
+use crate::rejects::REJECT_REASON_COLUMN;
+
+/// Parsed CSV rows keyed by column name
+pub(crate) type CsvRows = Vec<HashMap<String, String>>;
+
+/// Decompressed tar stream of a `.tar.gz` and how far into it it has been read
+type TarStream = (Box<dyn Read + Send>, u64);
+
+/// Index of each tar archive read in this process, built on its first use
+static ARCHIVE_INDEXES: OnceLock<Mutex<HashMap<PathBuf, Arc<ArchiveIndex>>>> = OnceLock::new();
+
+/// Where the file entries of a tar archive are, from a single pass over it
+struct ArchiveIndex {
+    /// File name (inner directories flattened), offset of the data in the uncompressed tar
+    /// and size of each file entry, in archive order
+    entries: Vec<(OsString, u64, u64)>,
+    /// Streams of a `.tar.gz` left behind by finished entry readers. An entry further on
+    /// resumes the nearest one instead of decompressing the archive from the start.
+    parked: Arc<Mutex<Vec<TarStream>>>,
+}
+
+/// Reads one archive entry, parking its `.tar.gz` stream for reuse when dropped
+struct ArchiveEntryReader {
+    stream: Option<TarStream>,
+    /// Offset in the uncompressed tar where the entry ends
+    end: u64,
+    /// Where the stream goes back to, `None` for a plain `.tar` read by seeking
+    parked: Option<Arc<Mutex<Vec<TarStream>>>>,
+}
+
+impl Read for ArchiveEntryReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some((stream, position)) = &mut self.stream else {
+            return Ok(0);
+        };
+        let wanted = buf.len().min(self.end.saturating_sub(*position) as usize);
+        let read = stream.read(&mut buf[..wanted])?;
+        *position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Drop for ArchiveEntryReader {
+    fn drop(&mut self) {
+        if let (Some(stream), Some(parked)) = (self.stream.take(), &self.parked) {
+            let mut parked = parked.lock().unwrap_or_else(|e| e.into_inner());
+            // A few streams cover the passes that read the archive side by side
+            if parked.len() >= ArchiveIndex::MAX_PARKED {
+                parked.remove(0);
+            }
+            parked.push(stream);
+        }
+    }
+}
+
+impl ArchiveIndex {
+    const MAX_PARKED: usize = 4;
+    
+    /// The index of an archive, scanning it on first use
+    fn of(archive_path: &Path) -> Result<Arc<Self>> {
+        let indexes = ARCHIVE_INDEXES.get_or_init(Default::default);
+        if let Some(index) = indexes.lock().unwrap_or_else(|e| e.into_inner()).get(archive_path) {
+            return Ok(index.clone());
+        }
+        let mut entries = Vec::new();
+        let mut archive = tar::Archive::new(Self::tar_stream(archive_path)?);
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            if let Some(name) = entry.path()?.file_name() {
+                entries.push((name.to_os_string(), entry.raw_file_position(), entry.size()));
+            }
+        }
+        let index = Arc::new(Self { entries, parked: Arc::default() });
+        indexes.lock().unwrap_or_else(|e| e.into_inner()).insert(archive_path.to_path_buf(), index.clone());
+        Ok(index)
+    }
+    
+    /// Whether the archive is a plain `.tar`, whose entries can be read by seeking
+    fn is_plain_tar(archive_path: &Path) -> bool {
+        archive_path.to_string_lossy().to_lowercase().ends_with(".tar")
+    }
+    
+    /// The uncompressed tar stream of an archive, gunzipping unless it is a plain `.tar`
+    fn tar_stream(archive_path: &Path) -> Result<Box<dyn Read + Send>> {
+        let file = File::open(archive_path)
+            .map_err(|e| anyhow!("Failed to open archive {:?}: {}", archive_path, e))?;
+        if Self::is_plain_tar(archive_path) {
+            Ok(Box::new(file))
+        } else {
+            Ok(Box::new(flate2::read::GzDecoder::new(file)))
+        }
+    }
+    
+    /// Open the data of the entry `offset..offset + size`. A plain `.tar` seeks to it; a
+    /// `.tar.gz` resumes the furthest parked stream that hasn't passed it yet.
+    fn open_entry(&self, archive_path: &Path, offset: u64, size: u64) -> Result<ArchiveEntryReader> {
+        if Self::is_plain_tar(archive_path) {
+            let mut file = File::open(archive_path)?;
+            file.seek(SeekFrom::Start(offset))?;
+            return Ok(ArchiveEntryReader { stream: Some((Box::new(file), offset)), end: offset + size, parked: None });
+        }
+        let resumed = {
+            let mut parked = self.parked.lock().unwrap_or_else(|e| e.into_inner());
+            parked.iter().enumerate()
+                .filter(|(_, (_, position))| *position <= offset)
+                .max_by_key(|(_, (_, position))| *position)
+                .map(|(i, _)| i)
+                .map(|i| parked.remove(i))
+        };
+        let (mut stream, position) = match resumed {
+            Some(stream) => stream,
+            None => (Self::tar_stream(archive_path)?, 0),
+        };
+        std::io::copy(&mut stream.by_ref().take(offset - position), &mut std::io::sink())?;
+        Ok(ArchiveEntryReader { stream: Some((stream, offset)), end: offset + size, parked: Some(self.parked.clone()) })
+    }
+}
+
+/// A CSV data row as it appeared in the source file, kept for error reporting
+#[derive(Debug, Clone)]
+pub(crate) struct RawCsvRecord {
+    /// Line number of the record within its file (the header is line 1)
+    pub(crate) line: u64,
+    /// Unparsed field values
+    pub(crate) record: StringRecord,
+    /// Columns whose field was enclosed in quotes in the source (only tracked with `--quoted-as-string`)
+    pub(crate) quoted_columns: HashSet<String>,
+}
+
+impl RawCsvRecord {
+    /// Determine which fields of the record starting at `bytes` were enclosed in quotes.
+    /// Parsed records no longer carry this, so the source bytes are re-scanned.
+    fn quoted_fields(bytes: &[u8], field_count: usize, delimiter: u8) -> Vec<bool> {
+        let mut quoted = Vec::with_capacity(field_count);
+        let mut i = 0;
+        
+        while quoted.len() < field_count {
+            let is_quoted = bytes.get(i) == Some(&b'"');
+            quoted.push(is_quoted);
+            if is_quoted {
+                // Skip the quoted section, treating "" as an escaped quote
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == b'"' {
+                        if bytes.get(i + 1) == Some(&b'"') {
+                            i += 2;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            while i < bytes.len() && bytes[i] != delimiter && !matches!(bytes[i], b'\n' | b'\r') {
+                i += 1;
+            }
+            if bytes.get(i) != Some(&delimiter) {
+                break;
+            }
+            i += 1;
+        }
+        
+        quoted.resize(field_count, false);
+        quoted
+    }
+    
+    /// Quote a CSV field if it needs quoting, or always when `force` is set
+    pub(crate) fn csv_field(field: &str, force: bool, delimiter: u8) -> String {
+        if force || field.contains([char::from(delimiter), '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+    
+    /// Render the record back to a single CSV line
+    pub(crate) fn to_csv_line(&self) -> String {
+        let mut writer = csv::WriterBuilder::new()
+            .terminator(csv::Terminator::Any(b'\n'))
+            .from_writer(Vec::new());
+        let bytes = writer.write_record(&self.record)
+            .ok()
+            .and_then(|_| writer.into_inner().ok());
+        match bytes {
+            Some(bytes) => String::from_utf8_lossy(&bytes).trim_end_matches('\n').to_string(),
+            None => self.record.iter().collect::<Vec<_>>().join(","),
+        }
+    }
+}
+
+/// Reads a CSV file a batch of rows at a time, so a file never has to fit in memory
+pub(crate) struct CsvBatchReader {
+    reader: Reader<Box<dyn Read>>,
+    pub(crate) headers: StringRecord,
+    /// Second pass over the source bytes for quote detection (only with `--quoted-as-string`),
+    /// with the offset it has been read up to
+    source: Option<(Box<dyn Read>, u64)>,
+    pub(crate) delimiter: u8,
+    pub(crate) trim_values: TrimValues,
+    /// `--null-value` sentinels, which skip the `--schema` check like empty values
+    pub(crate) null_values: Vec<String>,
+    pub(crate) file_name: String,
+    /// `--schema` types of this file's columns
+    pub(crate) column_types: HashMap<String, ColumnType>,
+}
+
+impl CsvBatchReader {
+    pub(crate) fn open(path: &Path, options: &LoaderOptions) -> Result<Self> {
+        let mut reader = FalkorDBCSVLoader::csv_reader(path, options.delimiter.0)?;
+        let headers = reader.headers()?.clone();
+        let source = if options.quoted_as_string {
+            Some((FalkorDBCSVLoader::open_csv(path)?, 0))
+        } else {
+            None
+        };
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let column_types = FalkorDBCSVLoader::entity_name_from_file_name(&file_name, "nodes_")
+            .or_else(|| FalkorDBCSVLoader::entity_name_from_file_name(&file_name, "edges_"))
+            .and_then(|name| options.schema.get(name))
+            .cloned()
+            .unwrap_or_default();
+        Ok(Self {
+            reader,
+            headers,
+            source,
+            delimiter: options.delimiter.0,
+            trim_values: options.trim_values,
+            null_values: options.null_values.clone(),
+            file_name,
+            column_types,
+        })
+    }
+    
+    /// Read up to `size` rows; an empty batch means the file is done
+    pub(crate) fn next_batch(&mut self, size: usize) -> Result<(CsvRows, Vec<RawCsvRecord>)> {
+        let mut rows = Vec::new();
+        let mut raw_records = Vec::new();
+        
+        while rows.len() < size {
+            let mut record = StringRecord::new();
+            if !self.reader.read_record(&mut record)? {
+                break;
+            }
+            let line = record.position().map_or(0, |pos| pos.line());
+            let mut quoted_columns: HashSet<String> = match (&mut self.source, record.position()) {
+                (Some((source, offset)), Some(pos)) => {
+                    // The record spans from its start to where the reader stopped after it
+                    let start = pos.byte();
+                    let end = self.reader.position().byte();
+                    std::io::copy(&mut source.by_ref().take(start.saturating_sub(*offset)), &mut std::io::sink())?;
+                    let mut bytes = Vec::new();
+                    source.by_ref().take(end.saturating_sub(start)).read_to_end(&mut bytes)?;
+                    *offset = end.max(start);
+                    self.headers.iter()
+                        .zip(RawCsvRecord::quoted_fields(&bytes, record.len(), self.delimiter))
+                        .filter(|(_, quoted)| *quoted)
+                        .map(|(column, _)| column.to_string())
+                        .collect()
+                }
+                _ => HashSet::new(),
+            };
+            let mut row = record.deserialize::<HashMap<String, String>>(Some(&self.headers))?;
+            row.remove(REJECT_REASON_COLUMN);
+            self.trim_values.apply(&mut row);
+            // A schema type replaces inference, so string columns load like quoted fields
+            // and the other types ignore quoting
+            for (column, column_type) in &self.column_types {
+                let Some(value) = row.get(column).filter(|value| !value.is_empty()) else {
+                    continue;
+                };
+                if self.null_values.contains(value) && !quoted_columns.contains(column) {
+                    continue;
+                }
+                if !column_type.accepts(value) {
+                    return Err(anyhow!(
+                        "Value {:?} in column '{}' of {} line {} is not a valid {} for --schema",
+                        value, column, self.file_name, line, column_type.name()
+                    ));
+                }
+                if *column_type == ColumnType::String {
+                    quoted_columns.insert(column.clone());
+                } else {
+                    quoted_columns.remove(column);
+                }
+            }
+            rows.push(row);
+            raw_records.push(RawCsvRecord { line, record, quoted_columns });
+        }
+        
+        Ok((rows, raw_records))
+    }
+}
+
+impl FalkorDBCSVLoader {
+    /// Whether `path` is a tar archive of CSV files (`.tar`, `.tar.gz` or `.tgz`)
+    pub(crate) fn is_csv_archive(path: &Path) -> bool {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        path.is_file() && (name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".tar"))
+    }
+    
+    /// List the files in a CSV directory. For a tar archive the entries are listed as
+    /// `<archive>/<entry file name>` (inner directories are flattened) without extracting anything.
+    pub(crate) fn list_csv_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        if Self::is_csv_archive(dir) {
+            files.extend(ArchiveIndex::of(dir)?.entries.iter().map(|(name, _, _)| dir.join(name)));
+        } else {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
+    }
+    
+    /// Byte size of each file, taken from the tar headers for files inside an archive
+    fn csv_file_sizes(files: &[PathBuf]) -> Result<HashMap<PathBuf, u64>> {
+        let mut sizes = HashMap::new();
+        for file in files {
+            if sizes.contains_key(file) {
+                continue;
+            }
+            match file.parent().filter(|parent| Self::is_csv_archive(parent)) {
+                Some(archive_path) => {
+                    // The first entry of a name is the one that is read
+                    for (name, _, size) in ArchiveIndex::of(archive_path)?.entries.iter().rev() {
+                        sizes.insert(archive_path.join(name), *size);
+                    }
+                }
+                None => {
+                    sizes.insert(file.clone(), std::fs::metadata(file)?.len());
+                }
+            }
+        }
+        Ok(sizes)
+    }
+    
+    /// Sort files for `--file-order`; files of equal size stay in name order
+    pub(crate) fn order_files(files: &mut [PathBuf], order: FileOrder) -> Result<()> {
+        files.sort();
+        if order != FileOrder::Name {
+            let sizes = Self::csv_file_sizes(files)?;
+            let size = |file: &PathBuf| sizes.get(file).copied().unwrap_or(0);
+            match order {
+                FileOrder::SizeDesc => files.sort_by_key(|file| Reverse(size(file))),
+                _ => files.sort_by_key(size),
+            }
+        }
+        Ok(())
+    }
+    
+    /// Open a CSV file, either on disk or as an entry of a tar archive, gunzipping `.csv.gz`
+    /// files on the fly
+    fn open_csv(path: &Path) -> Result<Box<dyn Read>> {
+        let reader = Self::open_raw_csv(path)?;
+        if path.to_string_lossy().to_lowercase().ends_with(".gz") {
+            Ok(Box::new(flate2::read::GzDecoder::new(reader)))
+        } else {
+            Ok(reader)
+        }
+    }
+    
+    /// Open the bytes of a CSV file as stored. Archive entries are located with the archive's
+    /// index and streamed from it, never extracted or buffered whole.
+    fn open_raw_csv(path: &Path) -> Result<Box<dyn Read>> {
+        if let Some(archive_path) = path.parent().filter(|parent| Self::is_csv_archive(parent)) {
+            let wanted = path.file_name().unwrap_or_default();
+            let index = ArchiveIndex::of(archive_path)?;
+            let Some((_, offset, size)) = index.entries.iter().find(|(name, _, _)| name == wanted) else {
+                return Err(anyhow!("{:?} not found in archive {:?}", wanted, archive_path));
+            };
+            return Ok(Box::new(index.open_entry(archive_path, *offset, *size)?));
+        }
+        Ok(Box::new(File::open(path)?))
+    }
+    
+    /// CSV reader over a file on disk or inside a tar archive, splitting fields on `delimiter`
+    pub(crate) fn csv_reader(path: &Path, delimiter: u8) -> Result<Reader<Box<dyn Read>>> {
+        Ok(csv::ReaderBuilder::new().delimiter(delimiter).from_reader(Self::open_csv(path)?))
+    }
+    
+    /// Whether an optional CSV file (indexes.csv, constraints.csv) is present
+    pub(crate) fn csv_file_exists(path: &Path) -> bool {
+        match path.parent().filter(|parent| Self::is_csv_archive(parent)) {
+            Some(archive_path) => Self::list_csv_dir(archive_path)
+                .is_ok_and(|files| files.iter().any(|file| file == path)),
+            None => path.exists(),
+        }
+    }
+    
+    /// Read a CSV file and return records as HashMap<String, String>
+    pub(crate) fn read_csv_file<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<HashMap<String, String>>> {
+        self.read_csv_file_with_raw(file_path).map(|(records, _)| records)
+    }
+    
+    /// Read a CSV file, returning the parsed records together with the raw record and
+    /// line number of each one (same order and length)
+    pub(crate) fn read_csv_file_with_raw<P: AsRef<Path>>(&self, file_path: P) -> Result<(CsvRows, Vec<RawCsvRecord>)> {
+        let (records, raw_records) = CsvBatchReader::open(file_path.as_ref(), &self.options)?.next_batch(usize::MAX)?;
+        
+        info!("  Read {} rows from {:?}", records.len(), file_path.as_ref());
+        Ok((records, raw_records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn archive_entries_are_read_from_the_stream() {
+        let dir = std::env::temp_dir().join(format!("falkordb-loader-archive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [("nodes_A.csv", "id\n1\n"), ("nodes_B.csv", "id,name\n2,Bob\n3,Carol\n"), ("edges_R.csv", "source,target\n2,3\n")];
+        for archive_name in ["export.tar", "export.tar.gz"] {
+            let archive_path = dir.join(archive_name);
+            let file: Box<dyn std::io::Write> = if archive_name.ends_with(".gz") {
+                Box::new(flate2::write::GzEncoder::new(File::create(&archive_path).unwrap(), flate2::Compression::default()))
+            } else {
+                Box::new(File::create(&archive_path).unwrap())
+            };
+            let mut builder = tar::Builder::new(file);
+            for (name, contents) in files {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, format!("export/{}", name), contents.as_bytes()).unwrap();
+            }
+            builder.into_inner().unwrap();
+            
+            let listed = FalkorDBCSVLoader::list_csv_dir(&archive_path).unwrap();
+            assert_eq!(listed, files.map(|(name, _)| archive_path.join(name)));
+            
+            // In archive order, out of order, partly read and again, as the loading passes do
+            let read = |name: &str| {
+                let mut contents = String::new();
+                FalkorDBCSVLoader::open_raw_csv(&archive_path.join(name)).unwrap().read_to_string(&mut contents).unwrap();
+                contents
+            };
+            for (name, contents) in files.iter().chain(files.iter().rev()) {
+                assert_eq!(read(name), *contents, "{} in {}", name, archive_name);
+            }
+            let mut partial = FalkorDBCSVLoader::open_raw_csv(&archive_path.join("nodes_A.csv")).unwrap();
+            partial.read_exact(&mut [0; 2]).unwrap();
+            drop(partial);
+            assert_eq!(read("nodes_B.csv"), files[1].1);
+            assert_eq!(read("nodes_A.csv"), files[0].1);
+            
+            let rows: Vec<_> = FalkorDBCSVLoader::csv_reader(&archive_path.join("edges_R.csv"), b',').unwrap().records().collect();
+            assert_eq!(rows.len(), 1);
+            assert!(FalkorDBCSVLoader::open_raw_csv(&archive_path.join("nodes_C.csv")).is_err());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
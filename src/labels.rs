@@ -0,0 +1,405 @@
+//! Label and relationship type names, file selection and label consistency checks.
+
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::loader::FalkorDBCSVLoader;
+use crate::options::{IdentifierStyle, LoaderOptions};
+
+impl FalkorDBCSVLoader {
+    /// Sanitize label by replacing invalid characters
+    pub(crate) fn sanitize_label(label: &str) -> String {
+        label.replace(':', "_")
+    }
+    
+    /// Derive the label name for a raw file-name label according to the identifier style
+    pub(crate) fn label_name(&self, raw_label: &str) -> String {
+        match self.options.identifier_style {
+            IdentifierStyle::Sanitize => Self::sanitize_label(raw_label),
+            IdentifierStyle::Backtick => raw_label.to_string(),
+        }
+    }
+    
+    /// Name of a node label in the graph: the `--normalize-label-case` convention, then
+    /// `--label-prefix` (on each part of a `A:B` multi-label unless backtick quoting keeps
+    /// the name whole)
+    pub(crate) fn graph_label(&self, label: &str) -> String {
+        let label = self.normalize_label_case(label);
+        match &self.options.label_prefix {
+            Some(prefix) if self.options.identifier_style == IdentifierStyle::Sanitize => label.split(':')
+                .map(|part| format!("{}{}", prefix, part))
+                .collect::<Vec<_>>()
+                .join(":"),
+            Some(prefix) => format!("{}{}", prefix, label),
+            None => label,
+        }
+    }
+    
+    /// Name of a relationship type in the graph: the casing convention, then `--rel-type-prefix`
+    fn graph_rel_type(&self, rel_type: &str) -> String {
+        let rel_type = self.normalize_label_case(rel_type);
+        match &self.options.rel_type_prefix {
+            Some(prefix) => format!("{}{}", prefix, rel_type),
+            None => rel_type,
+        }
+    }
+    
+    /// Render a node label for use inside a query
+    pub(crate) fn label_identifier(&self, label: &str) -> String {
+        self.quote_identifier(self.graph_label(label))
+    }
+    
+    /// Render a relationship type for use inside a query
+    pub(crate) fn rel_type_identifier(&self, rel_type: &str) -> String {
+        self.quote_identifier(self.graph_rel_type(rel_type))
+    }
+    
+    /// Quote a final label or relationship type name according to the identifier style
+    pub(crate) fn quote_identifier(&self, name: String) -> String {
+        match self.options.identifier_style {
+            IdentifierStyle::Sanitize => name,
+            IdentifierStyle::Backtick => format!("`{}`", name.replace('`', "``")),
+        }
+    }
+    
+    /// Apply the configured label casing, logging each distinct rename once
+    pub(crate) fn normalize_label_case(&self, name: &str) -> String {
+        let normalized = self.options.label_case.apply(name);
+        if normalized != name {
+            let mut logged = self.logged_normalizations.lock().unwrap_or_else(|e| e.into_inner());
+            if logged.insert(name.to_string()) {
+                info!("🔤 Normalized '{}' -> '{}'", name, normalized);
+            }
+        }
+        normalized
+    }
+    
+    /// Pick the label used to match an edge endpoint.
+    ///
+    /// `Network:Zone` style values are multi-labels and match on their first part, unless
+    /// backtick quoting preserved a node label that itself contains `:`.
+    pub(crate) fn primary_label<'a>(&self, label: &'a str) -> &'a str {
+        if self.options.identifier_style == IdentifierStyle::Backtick
+            && self.label_mapping.values().any(|l| l == label) {
+            return label;
+        }
+        label.split(':').next().unwrap_or(label)
+    }
+    
+    /// The single labels of a `A:B` multi-label; backtick quoting keeps a label with `:` whole
+    pub(crate) fn label_parts<'a>(&self, label: &'a str) -> Vec<&'a str> {
+        match self.options.identifier_style {
+            IdentifierStyle::Sanitize => label.split(':').collect(),
+            IdentifierStyle::Backtick => vec![label],
+        }
+    }
+    
+    /// Extract the raw label/type name from a `<prefix><NAME>.csv` or `<prefix><NAME>.csv.gz` file name.
+    ///
+    /// Sharded exports named `<prefix><NAME>.part-<N>.csv` (e.g. `nodes_Person.part-0001.csv`)
+    /// resolve to `<NAME>`, so every part file loads into the same label or relationship type.
+    /// Returns `None` when the file name doesn't follow the convention.
+    pub(crate) fn entity_name_from_file_name<'a>(file_name: &'a str, prefix: &str) -> Option<&'a str> {
+        let name = file_name.strip_prefix(prefix)?;
+        let name = name.strip_suffix(".gz").unwrap_or(name).strip_suffix(".csv")?;
+        let name = match name.find(".part-") {
+            Some(pos) => &name[..pos],
+            None => name,
+        };
+        if name.is_empty() {
+            None
+        } else {
+            Some(name)
+        }
+    }
+    
+    /// Whether a file is an unnamed `<kind>.csv` (or `<kind>.csv.gz`, `<kind>.part-<N>.csv`),
+    /// e.g. `nodes.csv`, whose rows take their label or type from a column
+    fn is_unnamed_file(file_name: &str, kind: &str) -> bool {
+        let name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+        name.strip_suffix(".csv")
+            .and_then(|name| name.strip_prefix(kind))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(".part-"))
+    }
+    
+    /// Whether a file holds nodes: `nodes_<Label>.csv`, or `nodes.csv` with `--label-column`
+    pub(crate) fn is_node_file(&self, file_name: &str) -> bool {
+        Self::entity_name_from_file_name(file_name, "nodes_").is_some()
+            || (self.options.label_column.is_some() && Self::is_unnamed_file(file_name, "nodes"))
+    }
+    
+    /// Whether a file holds edges: `edges_<TYPE>.csv`, or `edges.csv` with `--rel-type-column`
+    pub(crate) fn is_edge_file(&self, file_name: &str) -> bool {
+        Self::entity_name_from_file_name(file_name, "edges_").is_some()
+            || (self.options.rel_type_column.is_some() && Self::is_unnamed_file(file_name, "edges"))
+    }
+    
+    /// Validate and analyze label consistency between node and edge files
+    pub fn validate_label_consistency(&self) -> Result<HashMap<String, String>> {
+        info!("🔍 Validating label consistency between node and edge files...");
+        
+        // Get node labels from filenames, or from the --label-column of the node files
+        let mut node_labels = std::collections::HashSet::new();
+        let csv_files = self.list_selected_csv_files()?;
+        
+        for file_path in &csv_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            
+            if self.is_node_file(&file_name) {
+                for label in self.node_file_labels(file_path)? {
+                    node_labels.extend(self.label_parts(&label).into_iter().map(str::to_string));
+                    node_labels.insert(label);
+                }
+            }
+        }
+        
+        info!("📋 Found node labels: {:?}", node_labels.iter().collect::<Vec<_>>());
+        
+        // Node labels that differ only by case; edges can only refer to them exactly
+        let mut labels_by_lowercase: BTreeMap<String, BTreeSet<&String>> = BTreeMap::new();
+        for label in &node_labels {
+            labels_by_lowercase.entry(label.to_lowercase()).or_default().insert(label);
+        }
+        for colliding in labels_by_lowercase.values().filter(|labels| labels.len() > 1) {
+            warn!("⚠️ Node labels {:?} differ only by case; edge labels must match one of them exactly", colliding);
+        }
+        
+        // Get edge labels from edge files
+        let mut edge_labels = std::collections::HashSet::new();
+        let mut edge_label_files: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for file_path in &csv_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            
+            if self.is_edge_file(&file_name) {
+                // Read first data row to get labels
+                let mut rdr = Self::csv_reader(file_path, self.options.delimiter.0)?;
+                
+                if let Some(result) = rdr.deserialize::<HashMap<String, String>>().next() {
+                    let record = result?;
+                    if let (Some(source_label), Some(target_label)) = 
+                        (record.get("source_label"), record.get("target_label")) {
+                        edge_labels.insert(source_label.clone());
+                        edge_labels.insert(target_label.clone());
+                        for label in [source_label, target_label] {
+                            edge_label_files.entry(label.clone()).or_default().insert(file_name.clone());
+                        }
+                    }
+                }
+            }
+        }
+        
+        info!("📋 Found edge labels: {:?}", edge_labels.iter().collect::<Vec<_>>());
+        
+        // Create label mapping (case-insensitive matching)
+        // Note: Edge labels can be multi-label (e.g., "Network:Zone") which means the node has both labels
+        let mut label_mapping = HashMap::new();
+        let mut missing_labels = Vec::new();
+        let mut ambiguous_labels = Vec::new();
+        
+        for edge_label in &edge_labels {
+            let mut found = false;
+            
+            // Try exact match first
+            if node_labels.contains(edge_label) {
+                label_mapping.insert(edge_label.clone(), edge_label.clone());
+                found = true;
+            } else {
+                // Try case-insensitive match, which must be unique so the result doesn't depend
+                // on iteration order
+                match labels_by_lowercase.get(&edge_label.to_lowercase()) {
+                    Some(candidates) if candidates.len() > 1 => {
+                        ambiguous_labels.push(format!("'{}' matches {:?}", edge_label, candidates));
+                        found = true;
+                    }
+                    Some(candidates) => {
+                        if let Some(node_label) = candidates.first() {
+                            label_mapping.insert(edge_label.clone(), (*node_label).clone());
+                            info!("🔗 Mapped edge label '{}' -> node label '{}'", edge_label, node_label);
+                            found = true;
+                        }
+                    }
+                    None => {}
+                }
+                
+                // If still not found, check if it's a multi-label (e.g., "Network:Zone")
+                if !found && edge_label.contains(':') {
+                    // Check if all parts of the multi-label exist as node labels
+                    let label_parts: Vec<&str> = edge_label.split(':').collect();
+                    let all_parts_exist = label_parts.iter().all(|part| {
+                        // Check case-insensitive match for each part
+                        node_labels.iter().any(|nl| nl.to_lowercase() == part.to_lowercase())
+                    });
+                    
+                    if all_parts_exist {
+                        // Multi-label is valid - keep it as-is (no mapping needed)
+                        info!("✓ Multi-label '{}' is valid (all parts exist as node labels)", edge_label);
+                        found = true;
+                    }
+                }
+            }
+            
+            // Nodes of a label left out by --include-label / --exclude-label come from an earlier load
+            if !found && self.label_parts(edge_label).iter().any(|part| !Self::is_selected_label(&self.options, part)) {
+                info!("⏭️ Edge label '{}' is filtered out, expecting its nodes to exist already", edge_label);
+                found = true;
+            }
+            
+            if !found {
+                missing_labels.push(edge_label.clone());
+            }
+        }
+        
+        if !ambiguous_labels.is_empty() {
+            ambiguous_labels.sort();
+            for ambiguity in &ambiguous_labels {
+                error!("❌ Edge label {} case-insensitively; rename one of the node files or fix the edge label", ambiguity);
+            }
+            return Err(anyhow!("Label validation failed: ambiguous edge labels: {}", ambiguous_labels.join("; ")));
+        }
+        
+        if !missing_labels.is_empty() {
+            error!("❌ Found edge labels without corresponding node files: {:?}", missing_labels);
+            if self.options.suggest_fixes {
+                self.suggest_label_fixes(&missing_labels, &node_labels, &edge_label_files)?;
+            }
+            return Err(anyhow!("Label validation failed: missing node files for labels: {:?}", missing_labels));
+        }
+        
+        if label_mapping.is_empty() {
+            info!("✅ All labels match exactly");
+        } else {
+            info!("✅ Label validation complete. Mappings: {:?}", label_mapping);
+        }
+        
+        Ok(label_mapping)
+    }
+    
+    /// Log concrete fixes for edge labels that have no node file and print them as a JSON
+    /// remediation report: similar existing labels, an identifier style that would match,
+    /// or the node file to create
+    fn suggest_label_fixes(&self, missing_labels: &[String], node_labels: &HashSet<String>,
+                           edge_label_files: &BTreeMap<String, BTreeSet<String>>) -> Result<()> {
+        let mut node_labels: Vec<&String> = node_labels.iter().collect();
+        node_labels.sort();
+        let mut missing_labels = missing_labels.to_vec();
+        missing_labels.sort();
+        let mut report = Vec::new();
+        
+        for label in &missing_labels {
+            let files: Vec<&String> = edge_label_files.get(label).into_iter().flatten().collect();
+            let mut suggestions = Vec::new();
+            
+            // A typo or plural in the edge data: point at the closest existing labels
+            for node_label in &node_labels {
+                let distance = Self::edit_distance(&label.to_lowercase(), &node_label.to_lowercase());
+                if distance <= 2 {
+                    warn!("💡 '{}': did you mean '{}'? Change the source_label/target_label values in {:?}",
+                          label, node_label, files);
+                    suggestions.push(serde_json::json!({
+                        "action": "replace_edge_label",
+                        "replacement": node_label,
+                        "edit_distance": distance,
+                        "files": files,
+                    }));
+                }
+            }
+            
+            // A node file name containing ':' is sanitized to '_' unless names are backtick-quoted
+            if self.options.identifier_style == IdentifierStyle::Sanitize
+                && node_labels.iter().any(|node_label| **node_label == Self::sanitize_label(label)) {
+                warn!("💡 '{}': the node file for this label was sanitized to '{}'; rerun with --identifier-style backtick",
+                      label, Self::sanitize_label(label));
+                suggestions.push(serde_json::json!({
+                    "action": "set_option",
+                    "option": "--identifier-style backtick",
+                }));
+            }
+            
+            let node_file = format!("nodes_{}.csv", label);
+            warn!("💡 '{}': or create {} (with at least an id column) for the nodes referenced by {:?}",
+                  label, node_file, files);
+            suggestions.push(serde_json::json!({
+                "action": "create_node_file",
+                "file": node_file,
+                "files": files,
+            }));
+            
+            report.push(serde_json::json!({
+                "label": label,
+                "referenced_by": files,
+                "suggestions": suggestions,
+            }));
+        }
+        
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "missing_labels": report }))?);
+        Ok(())
+    }
+    
+    /// Whether `name` matches a glob `pattern`, where `*` matches any run of characters and
+    /// `?` a single character
+    fn glob_matches(pattern: &str, name: &str) -> bool {
+        let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+        let (mut p, mut n) = (0, 0);
+        // Position after the last `*` and the name position it was tried at, to backtrack to
+        let mut star: Option<(usize, usize)> = None;
+        while n < name.len() {
+            if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+                p += 1;
+                n += 1;
+            } else if p < pattern.len() && pattern[p] == '*' {
+                star = Some((p + 1, n));
+                p += 1;
+            } else if let Some((star_p, star_n)) = star {
+                p = star_p;
+                n = star_n + 1;
+                star = Some((star_p, star_n + 1));
+            } else {
+                return false;
+            }
+        }
+        pattern[p..].iter().all(|c| *c == '*')
+    }
+    
+    /// Whether a label or relationship type passes `--include-label` / `--exclude-label`
+    fn is_selected_label(options: &LoaderOptions, name: &str) -> bool {
+        !options.exclude_labels.iter().any(|pattern| Self::glob_matches(pattern, name))
+            && (options.include_labels.is_empty()
+                || options.include_labels.iter().any(|pattern| Self::glob_matches(pattern, name)))
+    }
+    
+    /// Whether a file is loaded under `--include-label` / `--exclude-label`. Node and edge files
+    /// go by the label or relationship type in their name; unnamed `nodes.csv` / `edges.csv`
+    /// files and definition files are always kept.
+    pub(crate) fn is_selected_file(options: &LoaderOptions, file_name: &str) -> bool {
+        match Self::entity_name_from_file_name(file_name, "nodes_")
+            .or_else(|| Self::entity_name_from_file_name(file_name, "edges_")) {
+            Some(name) => Self::is_selected_label(options, name),
+            None => true,
+        }
+    }
+    
+    /// Files of the CSV directory, without the node and edge files `--include-label` /
+    /// `--exclude-label` filter out
+    pub(crate) fn list_selected_csv_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Self::list_csv_dir(&self.csv_dir)?;
+        files.retain(|path| Self::is_selected_file(&self.options, &path.file_name().unwrap_or_default().to_string_lossy()));
+        Ok(files)
+    }
+    
+    /// Levenshtein distance between two strings, by character
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, cb) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(ca != *cb);
+                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+            previous = current;
+        }
+        previous[b.len()]
+    }
+}
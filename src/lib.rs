@@ -0,0 +1,24 @@
+//! Load CSV files into FalkorDB. The `falkordb-loader` binary is a command line front end
+//! for [`FalkorDBCSVLoader`]; embedders build one directly and configure it with [`LoaderOptions`].
+
+mod edges;
+mod files;
+mod labels;
+mod literals;
+mod loader;
+mod metrics;
+mod nodes;
+mod options;
+mod pipeline;
+mod plan;
+mod rejects;
+mod rows;
+mod schema;
+mod verify;
+
+pub use loader::FalkorDBCSVLoader;
+pub use options::{
+    BatchHook, BatchHookFn, BatchKind, ColumnType, Delimiter, FileOrder, IdentifierStyle, LabelCase, LoaderOptions,
+    NullHandling, OnDuplicateId, PlanFormat, StatsFormat, TrimValues,
+};
+pub use verify::{GraphStats, LabelCount, RelTypeCount};
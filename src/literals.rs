@@ -0,0 +1,528 @@
+//! Parsing CSV values and rendering them as Cypher literals and batch parameters.
+
+use anyhow::Result;
+use falkordb::FalkorValue;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::files::RawCsvRecord;
+use crate::loader::FalkorDBCSVLoader;
+use crate::options::{ColumnType, LoaderOptions, NullHandling};
+
+impl FalkorDBCSVLoader {
+    /// Cypher literal of a row's `id`, `source` or `target` column. Batch queries and their
+    /// per-row fallback both use it, so a fallback matches the ids a batch stored: `123` is an
+    /// integer in both unless it was quoted under `--quoted-as-string`.
+    pub(crate) fn row_id_literal(row: &HashMap<String, String>, raw: &RawCsvRecord, column: &str) -> String {
+        Self::typed_cypher_literal(row.get(column).map_or("", String::as_str), raw.quoted_columns.contains(column))
+    }
+    
+    /// Whether an unquoted CSV value is one of the `--null-value` sentinels
+    pub(crate) fn is_explicit_null(value: &str, quoted: bool, options: &LoaderOptions) -> bool {
+        !quoted && options.null_values.iter().any(|null_value| null_value == value)
+    }
+    
+    /// Value to put in a row's property map. An explicit null becomes an empty value, which
+    /// renders as `null` and so removes the property on `SET +=`, or is left out entirely
+    /// (`None`) with `--null-handling keep`.
+    pub(crate) fn property_value(value: &str, quoted: bool, options: &LoaderOptions) -> Option<String> {
+        if !Self::is_explicit_null(value, quoted, options) {
+            return Some(value.to_string());
+        }
+        match options.null_handling {
+            NullHandling::Remove => Some(String::new()),
+            NullHandling::Keep => None,
+        }
+    }
+    
+    /// Cypher float literal. Debug formatting keeps a decimal point, so a weight of `3` is sent
+    /// as `3.0` and stored as a float whichever query sends it.
+    pub(crate) fn float_literal(value: f64) -> String {
+        format!("{:?}", value)
+    }
+    
+    /// Convert a value to Cypher literal syntax, forcing a string literal for quoted source fields
+    pub(crate) fn typed_cypher_literal(value: &str, quoted: bool) -> String {
+        if quoted {
+            Self::string_literal(value)
+        } else {
+            Self::value_to_cypher_literal(value)
+        }
+    }
+    
+    /// Like `typed_cypher_literal` for the value of property `key`. A `--schema` type in
+    /// `types` comes first (string columns arrive as quoted); otherwise an `--array-column`
+    /// becomes a list, and the boolean and temporal inference of `--infer-bools` and
+    /// `--infer-dates` is applied to unquoted values.
+    pub(crate) fn property_literal(key: &str, value: &str, quoted: bool, options: &LoaderOptions, types: Option<&HashMap<String, ColumnType>>) -> String {
+        if let Some(elements) = Self::array_elements(key, value, options, types) {
+            let elements: Vec<String> = elements.into_iter()
+                .map(|element| Self::scalar_property_literal(element, false, options))
+                .collect();
+            return format!("[{}]", elements.join(", "));
+        }
+        match types.and_then(|types| types.get(key)) {
+            Some(ColumnType::Int) => if let Ok(number) = value.parse::<i64>() {
+                return number.to_string();
+            },
+            Some(ColumnType::Float) => if let Ok(number) = value.parse::<f64>() {
+                // Debug formatting keeps a decimal point so whole numbers stay floats
+                return format!("{:?}", number);
+            },
+            Some(ColumnType::Bool) => if let Some(b) = Self::parse_bool(value) {
+                return b.to_string();
+            },
+            Some(ColumnType::Date) => if let Some(temporal) = Self::parse_temporal(value) {
+                return temporal;
+            },
+            _ => {}
+        }
+        Self::scalar_property_literal(value, quoted, options)
+    }
+    
+    /// Elements of a non-empty `--array-column` or `list` schema value: split on the column's
+    /// separator (`|` by default) after removing one pair of enclosing `[]`, trimmed, empty
+    /// elements left out
+    pub(crate) fn array_elements<'a>(key: &str, value: &'a str, options: &LoaderOptions, types: Option<&HashMap<String, ColumnType>>) -> Option<Vec<&'a str>> {
+        let separator = match options.array_columns.get(key) {
+            Some(separator) => separator.as_str(),
+            None if types.and_then(|types| types.get(key)) == Some(&ColumnType::List) => "|",
+            None => return None,
+        };
+        if value.is_empty() {
+            return None;
+        }
+        let inner = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')).unwrap_or(value);
+        Some(inner.split(separator).map(str::trim).filter(|e| !e.is_empty()).collect())
+    }
+    
+    /// Literal for a single value with `--infer-bools` and `--infer-dates` applied
+    fn scalar_property_literal(value: &str, quoted: bool, options: &LoaderOptions) -> String {
+        if let Some(b) = Self::inferred_bool(value, quoted, options) {
+            return b.to_string();
+        }
+        if let Some(temporal) = Self::inferred_temporal(value, quoted, options) {
+            return temporal;
+        }
+        Self::typed_cypher_literal(value, quoted)
+    }
+    
+    /// Boolean an unquoted property value is loaded as with `--infer-bools`
+    pub(crate) fn inferred_bool(value: &str, quoted: bool, options: &LoaderOptions) -> Option<bool> {
+        if !options.infer_bools || quoted {
+            return None;
+        }
+        Self::parse_bool(value)
+    }
+    
+    /// `true`/`false` in any case
+    pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+        if value.eq_ignore_ascii_case("true") {
+            Some(true)
+        } else if value.eq_ignore_ascii_case("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+    
+    /// Temporal constructor an unquoted property value is loaded as with `--infer-dates`
+    pub(crate) fn inferred_temporal(value: &str, quoted: bool, options: &LoaderOptions) -> Option<String> {
+        if !options.infer_dates || quoted {
+            return None;
+        }
+        Self::parse_temporal(value)
+    }
+    
+    /// Cypher temporal constructor for an ISO-8601 value: `date('2024-01-15')` for a date,
+    /// `localdatetime('2024-01-15T10:30:00')` for a date and time. A UTC offset or `Z` is
+    /// applied and the time stored in UTC, since FalkorDB has no zoned datetime type.
+    /// Anything else, including `2024-1-5` or `01/02/2024`, is not a temporal value.
+    pub(crate) fn parse_temporal(value: &str) -> Option<String> {
+        // Four-digit year, two-digit month and day, so partial or local formats never match
+        let date_part = value.get(..10)?.as_bytes();
+        let is_iso_date = date_part.iter().enumerate()
+            .all(|(i, b)| if i == 4 || i == 7 { *b == b'-' } else { b.is_ascii_digit() });
+        if !is_iso_date {
+            return None;
+        }
+        
+        if value.len() == 10 {
+            let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            return Some(format!("date('{}')", date.format("%Y-%m-%d")));
+        }
+        let local = if let Ok(zoned) = chrono::DateTime::parse_from_rfc3339(value) {
+            zoned.naive_utc()
+        } else {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+                .ok()?
+        };
+        Some(format!("localdatetime('{}')", local.format("%Y-%m-%dT%H:%M:%S%.f")))
+    }
+    
+    /// A value returned by the graph written as the literal `typed_cypher_literal` sends for
+    /// it, e.g. `1` for both `1` and `1.0` and `'007'` for a string
+    pub(crate) fn falkor_value_literal(value: &FalkorValue) -> String {
+        match value {
+            FalkorValue::String(s) => Self::string_literal(s),
+            other => Self::falkor_value_to_string(other),
+        }
+    }
+    
+    /// Escape and quote a value as a Cypher string literal. Line breaks and tabs are written
+    /// as escapes so multi-line fields keep the query on one line.
+    pub(crate) fn string_literal(value: &str) -> String {
+        let escaped = value.replace("\\", "\\\\")
+            .replace("'", "\\'")
+            .replace("\n", "\\n")
+            .replace("\r", "\\r")
+            .replace("\t", "\\t");
+        format!("'{}'", escaped)
+    }
+    
+    /// Convert a value to Cypher literal syntax
+    pub(crate) fn value_to_cypher_literal(value: &str) -> String {
+        if value.is_empty() {
+            return "null".to_string();
+        }
+        
+        // Keep formatting a number would lose, e.g. the zeros of account number 007
+        if Self::is_formatted_number(value) {
+            return Self::string_literal(value);
+        }
+        
+        // Try to parse as integer
+        if let Ok(num) = value.parse::<i64>() {
+            return num.to_string();
+        }
+        
+        // Try to parse as float
+        if let Ok(num) = value.parse::<f64>() {
+            return num.to_string();
+        }
+        
+        // Escape and quote as string
+        Self::string_literal(value)
+    }
+    
+    /// Whether a value would lose part of its text as a number: a leading zero followed by
+    /// another digit (`007`, `-01.5`), a leading `+`, or surrounding whitespace. Such values
+    /// are never inferred as numbers.
+    pub(crate) fn is_formatted_number(value: &str) -> bool {
+        let digits = value.strip_prefix('-').unwrap_or(value).as_bytes();
+        value.starts_with('+')
+            || value.trim() != value
+            || (digits.first() == Some(&b'0') && digits.get(1).is_some_and(u8::is_ascii_digit))
+    }
+    
+    /// Column positions used to order generated properties. Empty (so keys sort by name)
+    /// unless `--preserve-insertion-order` is set; cleaned `X:X` headers map to `X` as well.
+    pub(crate) fn column_positions<P: AsRef<Path>>(&self, file_path: P) -> Result<HashMap<String, usize>> {
+        let mut positions = HashMap::new();
+        if !self.options.preserve_insertion_order {
+            return Ok(positions);
+        }
+        
+        let headers = Self::csv_reader(file_path.as_ref(), self.options.delimiter.0)?.headers()?.clone();
+        for (position, header) in headers.iter().enumerate() {
+            if let Some((first, second)) = header.split_once(':') {
+                if first == second {
+                    positions.entry(first.to_string()).or_insert(position);
+                }
+            }
+            positions.insert(header.to_string(), position);
+        }
+        Ok(positions)
+    }
+    
+    /// Entries of a row or property map in a deterministic order: by column position, then name
+    pub(crate) fn ordered_entries<'a, V>(map: &'a HashMap<String, V>, positions: &HashMap<String, usize>) -> Vec<(&'a String, &'a V)> {
+        let mut entries: Vec<(&String, &V)> = map.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| {
+            let position = |key: &String| positions.get(key).copied().unwrap_or(usize::MAX);
+            position(a).cmp(&position(b)).then_with(|| a.cmp(b))
+        });
+        entries
+    }
+    
+    /// Build Cypher map literal from properties HashMap; keys in `string_keys` are always strings
+    pub(crate) fn build_cypher_map(properties: &HashMap<String, String>, string_keys: &HashSet<String>, positions: &HashMap<String, usize>,
+                        options: &LoaderOptions, types: Option<&HashMap<String, ColumnType>>) -> String {
+        if properties.is_empty() {
+            return "{}".to_string();
+        }
+        
+        let props: Vec<String> = Self::ordered_entries(properties, positions)
+            .into_iter()
+            .map(|(k, v)| format!("{}: {}", k, Self::property_literal(k, v, string_keys.contains(k), options, types)))
+            .collect();
+        
+        format!("{{{}}}", props.join(", "))
+    }
+    
+    /// JSON counterpart of `typed_cypher_literal` for the batch hook. Non-finite floats,
+    /// which JSON can't represent, stay strings.
+    pub(crate) fn typed_json_value(value: &str, quoted: bool) -> serde_json::Value {
+        if value.is_empty() {
+            return serde_json::Value::Null;
+        }
+        if !quoted && !Self::is_formatted_number(value) {
+            if let Ok(number) = value.parse::<i64>() {
+                return number.into();
+            }
+            if let Some(number) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+                return number.into();
+            }
+        }
+        value.into()
+    }
+    
+    /// JSON object of a property map for the batch hook, with `--array-column` values as
+    /// arrays. JSON has no temporal type, so `--infer-dates` values stay strings.
+    pub(crate) fn json_map(properties: &HashMap<String, String>, string_keys: &HashSet<String>, options: &LoaderOptions,
+                types: Option<&HashMap<String, ColumnType>>) -> serde_json::Value {
+        properties.iter()
+            .map(|(key, value)| {
+                let quoted = string_keys.contains(key);
+                let value = match (Self::array_elements(key, value, options, types), types.and_then(|types| types.get(key))) {
+                    (Some(elements), _) => elements.into_iter()
+                        .map(|element| Self::scalar_json_value(element, false, options))
+                        .collect::<Vec<_>>()
+                        .into(),
+                    (None, Some(ColumnType::Int)) => value.parse::<i64>()
+                        .map_or_else(|_| Self::scalar_json_value(value, quoted, options), Into::into),
+                    (None, Some(ColumnType::Float)) => value.parse::<f64>().ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map_or_else(|| Self::scalar_json_value(value, quoted, options), Into::into),
+                    (None, Some(ColumnType::Bool)) => Self::parse_bool(value)
+                        .map_or_else(|| Self::scalar_json_value(value, quoted, options), Into::into),
+                    (None, _) => Self::scalar_json_value(value, quoted, options),
+                };
+                (key.clone(), value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+    
+    /// `typed_json_value` with `--infer-bools` applied
+    fn scalar_json_value(value: &str, quoted: bool, options: &LoaderOptions) -> serde_json::Value {
+        match Self::inferred_bool(value, quoted, options) {
+            Some(b) => b.into(),
+            None => Self::typed_json_value(value, quoted),
+        }
+    }
+    
+    /// Render a JSON value as a Cypher literal. Numbers print like `value_to_cypher_literal`
+    /// and object keys are used as property names unchanged.
+    pub(crate) fn json_to_cypher_literal(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::Null => "null".to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            serde_json::Value::Number(number) => match number.as_i64() {
+                Some(number) => number.to_string(),
+                None => number.as_f64().unwrap_or_default().to_string(),
+            },
+            serde_json::Value::String(text) => Self::string_literal(text),
+            serde_json::Value::Array(items) => {
+                format!("[{}]", items.iter().map(Self::json_to_cypher_literal).collect::<Vec<_>>().join(", "))
+            }
+            serde_json::Value::Object(entries) => {
+                let entries: Vec<String> = entries.iter()
+                    .map(|(key, value)| format!("{}: {}", key, Self::json_to_cypher_literal(value)))
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+        }
+    }
+    
+    /// Render a scalar FalkorDB result value the way it appears in the CSV source
+    pub(crate) fn falkor_value_to_string(value: &FalkorValue) -> String {
+        match value {
+            FalkorValue::String(s) => s.clone(),
+            FalkorValue::I64(i) => i.to_string(),
+            FalkorValue::F64(f) => f.to_string(),
+            FalkorValue::Bool(b) => b.to_string(),
+            FalkorValue::None => String::new(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn formatted_numbers_are_detected() {
+        for value in ["007", "-01.5", "+5", "+1.5", " 5", "5 ", "00"] {
+            assert!(FalkorDBCSVLoader::is_formatted_number(value), "{:?}", value);
+        }
+        for value in ["0", "0.5", "-0.5", "10", "1e5", "1,234", "abc", ""] {
+            assert!(!FalkorDBCSVLoader::is_formatted_number(value), "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn cypher_literals_keep_number_like_strings() {
+        let cases = [
+            ("", "null"),
+            ("42", "42"),
+            ("-7", "-7"),
+            ("0", "0"),
+            ("0.5", "0.5"),
+            ("1e5", "100000"),
+            ("1.5E-3", "0.0015"),
+            ("007", "'007'"),
+            ("+5", "'+5'"),
+            (" 5", "' 5'"),
+            ("1,234", "'1,234'"),
+            ("1,2,3", "'1,2,3'"),
+            ("12abc", "'12abc'"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(FalkorDBCSVLoader::value_to_cypher_literal(value), expected, "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn temporal_values_accepted_formats() {
+        let cases = [
+            ("2024-01-15", "date('2024-01-15')"),
+            ("2024-02-29", "date('2024-02-29')"),
+            ("2024-01-15T10:30:00", "localdatetime('2024-01-15T10:30:00')"),
+            ("2024-01-15 10:30:00", "localdatetime('2024-01-15T10:30:00')"),
+            ("2024-01-15T10:30:00.250", "localdatetime('2024-01-15T10:30:00.250')"),
+            ("2024-01-15 10:30:00.5", "localdatetime('2024-01-15T10:30:00.500')"),
+            ("2024-01-15T10:30:00Z", "localdatetime('2024-01-15T10:30:00')"),
+            ("2024-01-15T10:30:00+02:00", "localdatetime('2024-01-15T08:30:00')"),
+            ("2024-01-15T01:30:00+02:00", "localdatetime('2024-01-14T23:30:00')"),
+            ("2024-01-15T23:30:00-01:00", "localdatetime('2024-01-16T00:30:00')"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(FalkorDBCSVLoader::parse_temporal(value).as_deref(), Some(expected), "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn temporal_values_rejected() {
+        for value in [
+            "", "2024", "2024-1-5", "01/02/2024", "15-01-2024", "2024/01/15",
+            "2024-13-01", "2023-02-29", "2024-01-15T25:00:00", "2024-01-15T10:30",
+            "2024-01-15x", "2024-01-15 noon", "20240115", "hello world",
+        ] {
+            assert_eq!(FalkorDBCSVLoader::parse_temporal(value), None, "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn parse_bool_table() {
+        let cases = [
+            ("true", Some(true)),
+            ("false", Some(false)),
+            ("TRUE", Some(true)),
+            ("False", Some(false)),
+            ("tRuE", Some(true)),
+            // Only true/false are booleans; everything else stays as loaded
+            ("yes", None),
+            ("no", None),
+            ("1", None),
+            ("0", None),
+            ("t", None),
+            ("Y", None),
+            (" true", None),
+            ("false ", None),
+            ("", None),
+            ("truefalse", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(FalkorDBCSVLoader::parse_bool(value), expected, "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn inferred_bool_needs_flag_and_unquoted_value() {
+        let mut options = LoaderOptions::default();
+        assert_eq!(FalkorDBCSVLoader::inferred_bool("true", false, &options), None);
+        options.infer_bools = true;
+        assert_eq!(FalkorDBCSVLoader::inferred_bool("true", false, &options), Some(true));
+        assert_eq!(FalkorDBCSVLoader::inferred_bool("FALSE", false, &options), Some(false));
+        assert_eq!(FalkorDBCSVLoader::inferred_bool("true", true, &options), None);
+        assert_eq!(FalkorDBCSVLoader::inferred_bool("yes", false, &options), None);
+    }
+    
+    #[test]
+    fn string_literals_escape_quotes_backslashes_and_newlines() {
+        let options = LoaderOptions::default();
+        let cases = [
+            ("O'Brien", "'O\\'Brien'"),
+            ("C:\\temp", "'C:\\\\temp'"),
+            ("\\'", "'\\\\\\''"),
+            ("line1\nline2", "'line1\\nline2'"),
+            ("a\r\nb\tc", "'a\\r\\nb\\tc'"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(FalkorDBCSVLoader::property_literal("name", value, true, &options, None), expected, "{:?}", value);
+            assert_eq!(FalkorDBCSVLoader::property_literal("name", value, false, &options, None), expected, "{:?}", value);
+        }
+    }
+    
+    #[test]
+    fn empty_and_null_property_values() {
+        let mut options = LoaderOptions::default();
+        // Without --null-value an empty cell is empty and renders as null; NULL is a string
+        assert_eq!(FalkorDBCSVLoader::property_value("", false, &options).as_deref(), Some(""));
+        assert_eq!(FalkorDBCSVLoader::property_value("NULL", false, &options).as_deref(), Some("NULL"));
+        assert_eq!(FalkorDBCSVLoader::property_literal("name", "", false, &options, None), "null");
+        assert_eq!(FalkorDBCSVLoader::property_literal("name", "NULL", false, &options, None), "'NULL'");
+        
+        options.null_values = vec!["NULL".to_string(), "\\N".to_string()];
+        assert_eq!(FalkorDBCSVLoader::property_value("NULL", false, &options).as_deref(), Some(""));
+        assert_eq!(FalkorDBCSVLoader::property_value("\\N", false, &options).as_deref(), Some(""));
+        // A quoted sentinel is data, not a null
+        assert_eq!(FalkorDBCSVLoader::property_value("NULL", true, &options).as_deref(), Some("NULL"));
+        assert_eq!(FalkorDBCSVLoader::property_value("", false, &options).as_deref(), Some(""));
+        
+        options.null_handling = NullHandling::Keep;
+        assert_eq!(FalkorDBCSVLoader::property_value("NULL", false, &options), None);
+        assert_eq!(FalkorDBCSVLoader::property_value("\\N", false, &options), None);
+        assert_eq!(FalkorDBCSVLoader::property_value("NULL", true, &options).as_deref(), Some("NULL"));
+        assert_eq!(FalkorDBCSVLoader::property_value("", false, &options).as_deref(), Some(""));
+    }
+    
+    #[test]
+    fn returned_ids_match_the_literals_they_were_sent_as() {
+        let cases = [
+            ("1", false, FalkorValue::I64(1)),
+            ("1.0", false, FalkorValue::I64(1)),
+            ("1.5", false, FalkorValue::F64(1.5)),
+            ("007", false, FalkorValue::String("007".to_string())),
+            ("42", true, FalkorValue::String("42".to_string())),
+            ("o'brien", false, FalkorValue::String("o'brien".to_string())),
+        ];
+        for (id, quoted, returned) in cases {
+            assert_eq!(FalkorDBCSVLoader::falkor_value_literal(&returned),
+                       FalkorDBCSVLoader::typed_cypher_literal(id, quoted), "{:?}", id);
+        }
+        assert_ne!(FalkorDBCSVLoader::falkor_value_literal(&FalkorValue::String("1".to_string())),
+                   FalkorDBCSVLoader::typed_cypher_literal("1", false));
+    }
+    
+    #[test]
+    fn fallback_ids_are_typed_like_the_batch() {
+        let row: HashMap<String, String> = [("id", "123"), ("source", "007"), ("target", "abc")].into_iter()
+            .map(|(column, value)| (column.to_string(), value.to_string()))
+            .collect();
+        let mut raw = RawCsvRecord { line: 2, record: csv::StringRecord::new(), quoted_columns: HashSet::new() };
+        
+        // A numeric id is the integer the batch stored and returns, not the string '123'
+        let id = FalkorDBCSVLoader::row_id_literal(&row, &raw, "id");
+        assert_eq!(id, "123");
+        assert_eq!(id, FalkorDBCSVLoader::falkor_value_literal(&FalkorValue::I64(123)));
+        assert_eq!(FalkorDBCSVLoader::row_id_literal(&row, &raw, "source"), "'007'");
+        assert_eq!(FalkorDBCSVLoader::row_id_literal(&row, &raw, "target"), "'abc'");
+        
+        raw.quoted_columns.insert("id".to_string());
+        assert_eq!(FalkorDBCSVLoader::row_id_literal(&row, &raw, "id"), "'123'");
+    }
+}
@@ -0,0 +1,480 @@
+//! Run-wide counters, batch timings and column statistics, and how they are reported.
+
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{error, info, warn};
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::files::RawCsvRecord;
+use crate::loader::FalkorDBCSVLoader;
+
+/// Counters accumulated over the whole run
+#[derive(Debug)]
+pub(crate) struct LoadMetrics {
+    /// Start of the run, for the rate pushed by `--pushgateway-url`
+    #[cfg(feature = "pushgateway")]
+    started: Instant,
+    pub(crate) rows_loaded: AtomicUsize,
+    pub(crate) rows_failed: AtomicUsize,
+    pub(crate) conflicts: AtomicUsize,
+    /// Edge rows left out by `--verify-endpoints`
+    pub(crate) missing_endpoints: AtomicUsize,
+}
+
+impl Default for LoadMetrics {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "pushgateway")]
+            started: Instant::now(),
+            rows_loaded: AtomicUsize::new(0),
+            rows_failed: AtomicUsize::new(0),
+            conflicts: AtomicUsize::new(0),
+            missing_endpoints: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Timing of one executed batch; ordered by duration first so a heap keeps the slowest
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct BatchTiming {
+    pub(crate) duration: Duration,
+    pub(crate) file: String,
+    batch_index: usize,
+    pub(crate) rows: usize,
+}
+
+/// When `--progress-interval` log lines are due: each time the loaded row count reaches the
+/// next multiple of the interval, however many rows a batch adds
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProgressThreshold {
+    interval: usize,
+    pub(crate) next: usize,
+}
+
+impl ProgressThreshold {
+    pub(crate) fn new(interval: usize) -> Self {
+        Self { interval, next: interval }
+    }
+    
+    /// Whether `count` crossed the next threshold, which then moves past `count`
+    pub(crate) fn crossed(&mut self, count: usize) -> bool {
+        if self.interval == 0 || count < self.next {
+            return false;
+        }
+        self.next = (count / self.interval + 1) * self.interval;
+        true
+    }
+}
+
+/// HyperLogLog distinct-count sketch with 2^12 one-byte registers (about 1.6% standard error)
+#[derive(Debug, Clone)]
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    const PRECISION: u32 = 12;
+    
+    pub(crate) fn new() -> Self {
+        Self { registers: vec![0; 1 << Self::PRECISION] }
+    }
+    
+    pub(crate) fn insert(&mut self, value: &str) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+        let index = (hash >> (64 - Self::PRECISION)) as usize;
+        // The guard bit caps the rank when the remaining bits are all zero
+        let rank = ((hash << Self::PRECISION) | (1 << (Self::PRECISION - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+    
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+    }
+    
+    fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-i32::from(r))).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        // Linear counting is more accurate for small cardinalities
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+/// Streaming statistics for one column of a label or relationship type
+#[derive(Debug, Clone)]
+pub(crate) struct ColumnStats {
+    pub(crate) values: usize,
+    nulls: usize,
+    /// Numeric range, meaningful while every value parses as a number
+    numeric_range: Option<(f64, f64)>,
+    all_numeric: bool,
+    text_range: Option<(String, String)>,
+    distinct: HyperLogLog,
+}
+
+impl Default for ColumnStats {
+    fn default() -> Self {
+        Self {
+            values: 0,
+            nulls: 0,
+            numeric_range: None,
+            all_numeric: true,
+            text_range: None,
+            distinct: HyperLogLog::new(),
+        }
+    }
+}
+
+impl ColumnStats {
+    /// Record one value; `None` counts as a null
+    fn observe(&mut self, value: Option<&str>) {
+        let Some(value) = value else {
+            self.nulls += 1;
+            return;
+        };
+        self.values += 1;
+        self.distinct.insert(value);
+        
+        match value.parse::<f64>() {
+            Ok(number) if self.all_numeric => {
+                let (min, max) = self.numeric_range.unwrap_or((number, number));
+                self.numeric_range = Some((min.min(number), max.max(number)));
+            }
+            Ok(_) => {}
+            Err(_) => self.all_numeric = false,
+        }
+        match &mut self.text_range {
+            Some((min, max)) => {
+                if value < min.as_str() {
+                    *min = value.to_string();
+                } else if value > max.as_str() {
+                    *max = value.to_string();
+                }
+            }
+            None => self.text_range = Some((value.to_string(), value.to_string())),
+        }
+    }
+    
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.values += other.values;
+        self.nulls += other.nulls;
+        self.all_numeric &= other.all_numeric;
+        self.numeric_range = match (self.numeric_range, other.numeric_range) {
+            (Some((a_min, a_max)), Some((b_min, b_max))) => Some((a_min.min(b_min), a_max.max(b_max))),
+            (range, None) | (None, range) => range,
+        };
+        self.text_range = match (self.text_range.take(), &other.text_range) {
+            (Some((a_min, a_max)), Some((b_min, b_max))) => {
+                Some((a_min.min(b_min.clone()), a_max.max(b_max.clone())))
+            }
+            (range, None) => range,
+            (None, range) => range.clone(),
+        };
+        self.distinct.merge(&other.distinct);
+    }
+    
+    /// One-line summary, e.g. `1000 values, 120 null (10.7%), min 3, max 97, ~85 distinct`
+    pub(crate) fn summary(&self) -> String {
+        let total = self.values + self.nulls;
+        let null_percent = if total > 0 { self.nulls as f64 * 100.0 / total as f64 } else { 0.0 };
+        let range = match (&self.numeric_range, &self.text_range) {
+            (Some((min, max)), _) if self.all_numeric => format!(", min {}, max {}", min, max),
+            (_, Some((min, max))) => format!(", min {:?}, max {:?}", min, max),
+            _ => String::new(),
+        };
+        format!("{} values, {} null ({:.1}%){}, ~{:.0} distinct",
+                self.values, self.nulls, null_percent, range, self.distinct.estimate())
+    }
+}
+
+/// Order-independent checksum over entity fingerprints: the wrapping sum of their
+/// 64-bit FNV-1a hashes, plus the number of entities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Checksum {
+    pub(crate) sum: u64,
+    pub(crate) count: usize,
+}
+
+impl Checksum {
+    pub(crate) fn add(&mut self, fingerprint: &str) {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in fingerprint.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        self.sum = self.sum.wrapping_add(hash);
+        self.count += 1;
+    }
+    
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.sum = self.sum.wrapping_add(other.sum);
+        self.count += other.count;
+    }
+}
+
+impl FalkorDBCSVLoader {
+    /// A bar showing rows done/total, rate and ETA, when progress bars are shown
+    pub(crate) fn progress_bar(&self, name: &str, total_rows: usize) -> Option<ProgressBar> {
+        let multi = self.options.progress_bars.as_ref()?;
+        let style = ProgressStyle::with_template("{prefix:>24} [{bar:40}] {pos}/{len} rows ({per_sec}, ETA {eta})")
+            .expect("valid progress bar template")
+            .progress_chars("=> ");
+        let bar = multi.add(ProgressBar::new(total_rows as u64).with_style(style).with_prefix(name.to_string()));
+        Some(bar)
+    }
+    
+    /// Remember a batch timing if it is among the slowest `report_slowest_batches` so far
+    pub(crate) fn record_batch_timing(&self, file: &str, batch_index: usize, rows: usize, duration: Duration) {
+        let limit = self.options.report_slowest_batches;
+        if limit == 0 {
+            return;
+        }
+        
+        let mut heap = self.slowest_batches.lock().unwrap_or_else(|e| e.into_inner());
+        heap.push(Reverse(BatchTiming { duration, file: file.to_string(), batch_index, rows }));
+        if heap.len() > limit {
+            heap.pop();
+        }
+    }
+    
+    /// Resident set size of this process in bytes from `/proc/self/status`; `None` on platforms
+    /// without it, where `--max-rss-mb` has no effect
+    pub(crate) fn resident_set_bytes() -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let kilobytes = status.lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+    
+    /// `--max-rss-mb`: fail the load with a summary of where it stood instead of being
+    /// OOM-killed without diagnostics. `buffered_rows` is the number of rows held in memory.
+    pub(crate) fn check_memory_limit(&self, file_name: &str, buffered_rows: usize) -> Result<()> {
+        let Some(limit) = self.options.max_rss_bytes else {
+            return Ok(());
+        };
+        let Some(rss) = Self::resident_set_bytes() else {
+            return Ok(());
+        };
+        if rss <= limit {
+            return Ok(());
+        }
+        
+        error!("🛑 Resident memory {} MB exceeds --max-rss-mb {} while loading {} ({} rows buffered)",
+               rss / (1024 * 1024), limit / (1024 * 1024), file_name, buffered_rows);
+        error!("   Rows loaded so far: {}, failed: {}. Split {} into smaller files (e.g. nodes_<LABEL>.part-<N>.csv) or raise the limit",
+               self.metrics.rows_loaded.load(Ordering::Relaxed), self.metrics.rows_failed.load(Ordering::Relaxed), file_name);
+        self.terminate_on_error.store(true, Ordering::Relaxed);
+        Err(anyhow!("Memory limit of {} MB exceeded while loading {}", limit / (1024 * 1024), file_name))
+    }
+    
+    /// Append an executed query to the `--capture-bundle` directory as `<seq>.cypher` and
+    /// record it in `manifest.jsonl`. Capturing stops for the rest of the run when the size
+    /// limit is reached or a write fails; the load itself is never affected.
+    pub(crate) fn capture_query(&self, graph_name: &str, query: &str, error: Option<String>) {
+        let Some(dir) = &self.options.capture_bundle else {
+            return;
+        };
+        let mut captured = self.captured.lock().unwrap_or_else(|e| e.into_inner());
+        let Some((count, bytes)) = *captured else {
+            return;
+        };
+        if bytes + query.len() as u64 > self.options.capture_max_bytes {
+            warn!("⚠️ Capture bundle reached {} bytes after {} queries, capturing stopped", bytes, count);
+            *captured = None;
+            return;
+        }
+        
+        let seq = count + 1;
+        let file_name = format!("{:06}.cypher", seq);
+        let entry = serde_json::json!({
+            "seq": seq,
+            "graph": graph_name,
+            "file": file_name,
+            "ok": error.is_none(),
+            "error": error,
+        });
+        let written = std::fs::write(dir.join(&file_name), query).and_then(|_| {
+            use std::io::Write;
+            let mut manifest = std::fs::OpenOptions::new().append(true).create(true).open(dir.join("manifest.jsonl"))?;
+            writeln!(manifest, "{}", entry)
+        });
+        *captured = match written {
+            Ok(()) => Some((seq, bytes + query.len() as u64)),
+            Err(e) => {
+                warn!("⚠️ Failed to write capture bundle {:?}, capturing stopped: {}", dir, e);
+                None
+            }
+        };
+    }
+    
+    /// Hold back a batch of `rows` until the `--throughput-target` schedule allows it. Each
+    /// batch reserves `rows / target` seconds; time lost to a slow database is not made up
+    /// later, so the target is a ceiling rather than an average to catch up to.
+    pub(crate) async fn pace_throughput(&self, rows: usize) {
+        let Some(target) = self.options.throughput_target else {
+            return;
+        };
+        let wait = {
+            let mut schedule = self.throughput_schedule.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let send_at = schedule.0.map_or(now, |next| next.max(now));
+            schedule.0 = Some(send_at + Duration::from_secs_f64(rows as f64 / target as f64));
+            let wait = send_at - now;
+            if !wait.is_zero() && schedule.1.is_zero() {
+                info!("⏱️ Loading faster than --throughput-target {} rows/s, pacing batches", target);
+            }
+            schedule.1 += wait;
+            wait
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+    
+    /// Log the slowest recorded batches, slowest first, and reset the record for the next graph
+    pub(crate) fn report_slowest_batches(&self) {
+        let mut heap = self.slowest_batches.lock().unwrap_or_else(|e| e.into_inner());
+        if heap.is_empty() {
+            return;
+        }
+        
+        // Sorting Reverse values ascending yields the longest durations first
+        let timings = std::mem::take(&mut *heap).into_sorted_vec();
+        info!("🐢 Slowest {} batches:", timings.len());
+        for (rank, Reverse(timing)) in timings.iter().enumerate() {
+            info!("   {}. {} batch #{} ({} rows): {:?}",
+                  rank + 1, timing.file, timing.batch_index + 1, timing.rows, timing.duration);
+        }
+    }
+    
+    /// Add a row's values to per-column statistics; empty values and explicit nulls count as nulls
+    pub(crate) fn observe_column_stats(&self, stats: &mut BTreeMap<String, ColumnStats>, row: &HashMap<String, String>, raw: &RawCsvRecord) {
+        for (column, value) in row {
+            let is_null = value.is_empty() || Self::is_explicit_null(value, raw.quoted_columns.contains(column), &self.options);
+            stats.entry(column.clone()).or_default().observe((!is_null).then_some(value.as_str()));
+        }
+    }
+    
+    /// Fold a file's column statistics into those of its label or relationship type
+    pub(crate) fn merge_column_stats(&self, name: &str, file_stats: BTreeMap<String, ColumnStats>) {
+        let mut all_stats = self.column_stats.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = all_stats.entry(name.to_string()).or_default();
+        for (column, column_stats) in file_stats {
+            match stats.get_mut(&column) {
+                Some(existing) => existing.merge(&column_stats),
+                None => {
+                    stats.insert(column, column_stats);
+                }
+            }
+        }
+    }
+    
+    /// Log the collected column statistics per label and relationship type
+    pub(crate) fn report_column_stats(&self) {
+        let all_stats = std::mem::take(&mut *self.column_stats.lock().unwrap_or_else(|e| e.into_inner()));
+        if all_stats.is_empty() {
+            return;
+        }
+        
+        info!("📈 Column statistics:");
+        for (name, stats) in &all_stats {
+            for (column, column_stats) in stats {
+                info!("   {}.{}: {}", name, column, column_stats.summary());
+            }
+        }
+    }
+    
+    /// Push run metrics to the configured Prometheus pushgateway as gauges. Failures are
+    /// logged but never fail the load.
+    #[cfg(feature = "pushgateway")]
+    pub async fn push_metrics(&self, success: bool) {
+        let Some(base_url) = &self.options.pushgateway_url else {
+            return;
+        };
+        
+        let rows_loaded = self.metrics.rows_loaded.load(Ordering::Relaxed);
+        let rows_failed = self.metrics.rows_failed.load(Ordering::Relaxed);
+        let duration = self.metrics.started.elapsed().as_secs_f64();
+        let rows_per_second = if duration > 0.0 { rows_loaded as f64 / duration } else { 0.0 };
+        let graph = self.base_graph_name.replace('\\', "\\\\").replace('"', "\\\"");
+        
+        let mut body = String::new();
+        for (name, help, value) in [
+            ("falkordb_loader_rows_loaded", "Rows loaded so far", rows_loaded as f64),
+            ("falkordb_loader_rows_failed", "Rows that failed to load", rows_failed as f64),
+            ("falkordb_loader_duration_seconds", "Elapsed load time", duration),
+            ("falkordb_loader_rows_per_second", "Average load throughput", rows_per_second),
+            ("falkordb_loader_success", "1 unless the load failed", if success { 1.0 } else { 0.0 }),
+        ] {
+            body.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n{}{{graph=\"{}\"}} {}\n",
+                                   name, help, name, name, graph, value));
+        }
+        
+        let url = format!("{}/metrics/job/{}", base_url.trim_end_matches('/'), self.options.job_name);
+        let result = tokio::task::spawn_blocking(move || {
+            ureq::put(&url)
+                .set("Content-Type", "text/plain; version=0.0.4")
+                .send_string(&body)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result.map_err(|e| e.to_string()).and_then(|r| r) {
+            Ok(_) => info!("📈 Pushed load metrics to pushgateway"),
+            Err(e) => warn!("⚠️ Failed to push metrics to pushgateway: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn progress_threshold_single_jump_past_several_intervals() {
+        let mut progress = ProgressThreshold::new(100);
+        assert!(!progress.crossed(99));
+        assert!(progress.crossed(350));
+        // One log line for the jump, the next one only once 400 is reached
+        assert!(!progress.crossed(399));
+        assert!(progress.crossed(400));
+    }
+    
+    #[test]
+    fn progress_threshold_exact_boundaries() {
+        let mut progress = ProgressThreshold::new(100);
+        assert!(progress.crossed(100));
+        assert!(!progress.crossed(100));
+        assert!(!progress.crossed(199));
+        assert!(progress.crossed(200));
+        assert!(progress.crossed(300));
+    }
+    
+    #[test]
+    fn progress_threshold_interval_zero_never_fires() {
+        let mut progress = ProgressThreshold::new(0);
+        assert!(!progress.crossed(0));
+        assert!(!progress.crossed(1));
+        assert!(!progress.crossed(usize::MAX));
+    }
+}
@@ -0,0 +1,667 @@
+//! Loading node files.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use falkordb::FalkorValue;
+use futures::stream::{FuturesUnordered, StreamExt};
+use log::{error, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::files::{CsvBatchReader, CsvRows, RawCsvRecord};
+use crate::loader::FalkorDBCSVLoader;
+use crate::metrics::{Checksum, ColumnStats, ProgressThreshold};
+use crate::options::{BatchKind, ColumnType};
+use crate::rejects::TARGET_NOT_FOUND;
+use crate::rows::BatchMarkers;
+
+/// What every batch of one node file shares, borrowed by the batches in flight
+struct NodeFileBatches<'a> {
+    filename: &'a str,
+    pub(crate) positions: &'a HashMap<String, usize>,
+    pub(crate) source_stamp: &'a Option<(String, String)>,
+    pub(crate) column_types: Option<&'a HashMap<String, ColumnType>>,
+}
+
+/// What the batches of one label share. A file has a single label unless `--label-column`
+/// gives each row its own.
+struct NodeBatchLabel {
+    pub(crate) label: String,
+    label_ident: String,
+    label_property: Option<(String, String)>,
+    /// The file's extra `SET` clauses plus the label's `--label-as-property` one
+    extra_set: String,
+}
+
+impl FalkorDBCSVLoader {
+    /// `--detect-conflicts`: look up the nodes of a batch before it is written and report every
+    /// property whose stored value differs from the incoming one. New properties and new nodes
+    /// are not conflicts; an explicit null removing a stored value is.
+    async fn report_node_conflicts(&self, file_name: &str, label_ident: &str, incoming: &[(&RawCsvRecord, &str, HashMap<String, String>)]) -> Result<()> {
+        let ids: Vec<String> = incoming.iter()
+            .map(|(raw, id, _)| Self::typed_cypher_literal(id, raw.quoted_columns.contains("id")))
+            .collect();
+        let query = format!("UNWIND [{}] AS id MATCH (n:{} {{id: id}}) RETURN n.id, properties(n)", ids.join(", "), label_ident);
+        let stored: HashMap<String, HashMap<String, FalkorValue>> = self.execute_graph_query(&query).await?
+            .into_iter()
+            .filter_map(|row| match (row.first(), row.get(1)) {
+                (Some(id), Some(FalkorValue::Map(properties))) => Some((Self::canonical_stored_value(id), properties.clone())),
+                _ => None,
+            })
+            .collect();
+        if stored.is_empty() {
+            return Ok(());
+        }
+        
+        let mut conflicts = Vec::new();
+        for (raw, id, properties) in incoming {
+            let Some(existing) = stored.get(&Self::canonical_csv_value(id, raw.quoted_columns.contains("id"))) else {
+                continue;
+            };
+            let mut keys: Vec<&String> = properties.keys().collect();
+            keys.sort();
+            for key in keys {
+                let value = &properties[key];
+                let Some(existing_value) = existing.get(key) else {
+                    continue;
+                };
+                // An empty incoming value is an explicit null that removes the property
+                if value.is_empty() || !Self::stored_value_matches(value, raw.quoted_columns.contains(key), existing_value) {
+                    let incoming_value = if value.is_empty() { "null" } else { value.as_str() };
+                    conflicts.push([file_name.to_string(), raw.line.to_string(), id.to_string(), key.clone(),
+                                    Self::falkor_value_to_string(existing_value), incoming_value.to_string()]);
+                }
+            }
+        }
+        
+        for [_, line, id, property, existing, incoming] in &conflicts {
+            warn!("⚠️ Conflict in {} line {}: id {} property '{}' is {:?}, overwriting with {:?}",
+                  file_name, line, id, property, existing, incoming);
+        }
+        self.metrics.conflicts.fetch_add(conflicts.len(), Ordering::Relaxed);
+        if let (Some(output_path), false) = (&self.options.conflicts_output, conflicts.is_empty()) {
+            let file = std::fs::OpenOptions::new().append(true).create(true).open(output_path)?;
+            let mut writer = csv::Writer::from_writer(file);
+            for conflict in &conflicts {
+                writer.write_record(conflict)?;
+            }
+            writer.flush()?;
+        }
+        Ok(())
+    }
+    
+    /// Column holding the labels of each node row: `--label-column`, or else `labels`
+    pub(crate) fn labels_column(&self) -> &str {
+        self.options.label_column.as_deref().unwrap_or("labels")
+    }
+    
+    /// Label of a node row: the label of its file, followed by the `;`-separated labels of
+    /// its labels column, as a `A:B` multi-label
+    pub(crate) fn row_label(&self, row: &HashMap<String, String>, file_label: Option<&str>) -> Option<String> {
+        let mut labels: Vec<String> = file_label.map(|raw_label| self.label_name(raw_label)).into_iter().collect();
+        let cell = row.get(self.labels_column()).map_or("", String::as_str);
+        for part in cell.split(';').map(str::trim).filter(|part| !part.is_empty()) {
+            let label = self.label_name(part);
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        (!labels.is_empty()).then(|| labels.join(":"))
+    }
+    
+    /// Every label a node file loads into. A file with a labels column is scanned, since its
+    /// labels are only known from its rows.
+    pub(crate) fn node_file_labels(&self, path: &Path) -> Result<BTreeSet<String>> {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let file_label = Self::entity_name_from_file_name(&file_name, "nodes_");
+        let column = self.labels_column();
+        let mut labels = BTreeSet::new();
+        let mut rdr = Self::csv_reader(path, self.options.delimiter.0)?;
+        let Some(index) = rdr.headers()?.iter().position(|h| h == column) else {
+            labels.extend(file_label.map(|raw_label| self.label_name(raw_label)));
+            return Ok(labels);
+        };
+        for record in rdr.records() {
+            let record = record?;
+            let row = HashMap::from([(column.to_string(), record.get(index).unwrap_or("").to_string())]);
+            labels.extend(self.row_label(&row, file_label));
+        }
+        Ok(labels)
+    }
+    
+    /// Next batch of a node file with the labels its rows load into. Without a labels column
+    /// this is the file's next batch. With one, rows are held per label set until it has a
+    /// full batch, and the partial batches are emitted at the end of the file, so each UNWIND
+    /// query creates nodes of a single label set. Rows without a label are rejected.
+    fn next_node_batch(&self, batches: &mut CsvBatchReader, pending: &mut Vec<(String, CsvRows, Vec<RawCsvRecord>)>,
+                       file_name: &str, file_label: Option<&str>, size: usize) -> Result<(String, CsvRows, Vec<RawCsvRecord>)> {
+        if !batches.headers.iter().any(|h| h == self.labels_column()) {
+            let (rows, raw_records) = batches.next_batch(size)?;
+            let label = file_label.map(|raw_label| self.label_name(raw_label)).unwrap_or_default();
+            return Ok((label, rows, raw_records));
+        }
+        loop {
+            if let Some(index) = pending.iter().position(|(_, rows, _)| rows.len() >= size) {
+                let (label, rows, raw_records) = &mut pending[index];
+                let batch = (label.clone(), rows.drain(..size).collect(), raw_records.drain(..size).collect());
+                if rows.is_empty() {
+                    pending.remove(index);
+                }
+                return Ok(batch);
+            }
+            let (rows, raw_records) = batches.next_batch(size)?;
+            if rows.is_empty() {
+                return Ok(if pending.is_empty() { Default::default() } else { pending.remove(0) });
+            }
+            for (row, raw) in rows.into_iter().zip(raw_records) {
+                let Some(label) = self.row_label(&row, file_label) else {
+                    let column = self.labels_column();
+                    error!("❌ Node row without a label in {} line {}", file_name, raw.line);
+                    self.report_rejected_row(file_name, &raw, &format!("no label in column '{}'", column))?;
+                    continue;
+                };
+                match pending.iter_mut().find(|(pending_label, _, _)| *pending_label == label) {
+                    Some((_, rows, raw_records)) => {
+                        rows.push(row);
+                        raw_records.push(raw);
+                    }
+                    None => pending.push((label, vec![row], vec![raw])),
+                }
+            }
+        }
+    }
+    
+    /// What every batch of a node label shares, built once per label of a file
+    fn node_batch_label(&self, label: &str, file_extra_set: &str) -> NodeBatchLabel {
+        let label_property = self.options.label_as_property.as_ref()
+            .map(|property| (property.clone(), Self::string_literal(label)));
+        let mut extra_set = file_extra_set.to_string();
+        if let Some((property, value)) = &label_property {
+            extra_set.push_str(&format!(", n.{} = {}", property, value));
+        }
+        NodeBatchLabel {
+            label: label.to_string(),
+            label_ident: self.label_identifier(label),
+            label_property,
+            extra_set,
+        }
+    }
+    
+    /// Run the `--post-load-queries` of a label, in file order. The first failing query fails
+    /// the load, since later files may rely on what it derives.
+    pub(crate) async fn run_post_load_queries(&self, label: &str) -> Result<()> {
+        let Some(queries) = self.options.post_load_queries.get(label) else {
+            return Ok(());
+        };
+        for (i, query) in queries.iter().enumerate() {
+            let query = query.replace("{label}", &self.label_identifier(label));
+            info!("🪄 Running post-load query {}/{} for :{}", i + 1, queries.len(), label);
+            self.execute_graph_query(&query).await
+                .map_err(|e| anyhow!("Post-load query {} for label {} failed: {}\n   Query: {}", i + 1, label, e, query))?;
+        }
+        Ok(())
+    }
+    
+    /// Load nodes from CSV file in batches using UNWIND for better performance
+    pub async fn load_nodes_batch<P: AsRef<Path>>(&self, file_path: P, batch_size: usize) -> Result<()> {
+        let start_time = Instant::now();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] Loading nodes from {:?}...", timestamp, file_path.as_ref());
+        
+        // Extract label from filename
+        let filename = file_path.as_ref()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let file_label = Self::entity_name_from_file_name(&filename, "nodes_");
+        if !self.is_node_file(&filename) {
+            return Err(anyhow!("Not a node file: {:?}", file_path.as_ref()));
+        }
+        // What the file is called in progress and summary lines: its label, or with an
+        // unlabeled `nodes.csv` the file name
+        let name = file_label.map_or_else(|| filename.clone(), |raw_label| self.label_name(raw_label));
+        // Types come from the file's label, as the reader validates them by file
+        let column_types = file_label.and_then(|raw_label| self.options.schema.get(raw_label));
+        
+        // Rows are read one batch at a time, so only the batch being loaded is held in memory
+        let duplicate_lines = self.check_duplicate_ids(file_path.as_ref(), &filename, file_label)?;
+        let mut batches = CsvBatchReader::open(file_path.as_ref(), &self.options)?;
+        let mut pending = Vec::new();
+        let mut first_batch = self.next_node_batch(&mut batches, &mut pending, &filename, file_label, batch_size)?;
+        if first_batch.1.is_empty() {
+            if self.options.fail_on_empty_file {
+                return Err(anyhow!("File {:?} has no data rows", file_path.as_ref()));
+            }
+            return Ok(());
+        }
+        
+        // Debug: show CSV headers
+        if let Some(first_row) = first_batch.1.first() {
+            let headers: Vec<&String> = first_row.keys().collect();
+            info!("  CSV headers: {:?}", headers);
+        }
+        
+        let append_columns = self.append_list_columns(first_batch.1.first());
+        let has_vectors = self.has_vector_columns(first_batch.1.first());
+        let positions = self.column_positions(&file_path)?;
+        
+        // Extra SET clauses for append-list and vector columns and the source stamp
+        let mut extra_set = Self::append_list_set_clause("n", &append_columns);
+        if has_vectors {
+            extra_set.push_str(", n += row.vectors");
+        }
+        let source_stamp = self.source_stamp(&filename);
+        if let Some((property, value)) = &source_stamp {
+            extra_set.push_str(&format!(", n.{} = {}", property, value));
+        }
+        // Label-specific parts, shared by every batch of the label
+        let mut node_labels: HashMap<String, Arc<NodeBatchLabel>> = HashMap::new();
+        
+        let mut total_loaded = 0;
+        // Progress needs the row count up front, which takes a separate pass over the file
+        let total_records = if self.options.progress_bars.is_some() || self.progress_interval > 0 {
+            self.file_record_count(file_path.as_ref())
+        } else {
+            0
+        };
+        let file_bar = self.progress_bar(&filename, total_records);
+        let mut progress = ProgressThreshold::new(self.progress_interval);
+        let mut file_stats: HashMap<String, BTreeMap<String, ColumnStats>> = HashMap::new();
+        let mut file_checksums: HashMap<String, Checksum> = HashMap::new();
+        let file = &NodeFileBatches {
+            filename: &filename,
+            positions: &positions,
+            source_stamp: &source_stamp,
+            column_types,
+        };
+        let concurrency = self.options.concurrency.max(1);
+        let mut in_flight = FuturesUnordered::new();
+        
+        // Process in batches
+        for batch_num in 0.. {
+            let (label, mut batch, mut batch_raw) = if batch_num == 0 {
+                std::mem::take(&mut first_batch)
+            } else {
+                self.next_node_batch(&mut batches, &mut pending, &filename, file_label, batch_size)?
+            };
+            
+            // Wait for a batch to finish at the concurrency limit, and for all of them at the end of the file
+            while in_flight.len() >= concurrency || (batch.is_empty() && !in_flight.is_empty()) {
+                let Some(done) = in_flight.next().await else {
+                    break;
+                };
+                let (done_batch_num, rows, loaded, batch_start_time): (usize, usize, usize, Instant) = done?;
+                total_loaded += loaded;
+                
+                // Report progress for batch
+                if self.progress_interval > 0 && file_bar.is_none()
+                   && (progress.crossed(total_loaded) || total_loaded == total_records) {
+                    let percent = (total_loaded as f64 / total_records as f64) * 100.0;
+                    info!("📊 Progress: {:.1}% ({}/{}) {} nodes loaded", 
+                          percent, total_loaded, total_records, name);
+                }
+                
+                let batch_duration = batch_start_time.elapsed();
+                let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+                if let Some(bar) = &file_bar {
+                    bar.inc(rows as u64);
+                    if let Some(overall) = &self.overall_bar {
+                        overall.inc(rows as u64);
+                    }
+                } else {
+                    info!("[{}] Batch complete: Loaded {} nodes (Duration: {:?})", 
+                          timestamp, rows, batch_duration);
+                }
+                self.record_batch_timing(&filename, done_batch_num, rows, batch_duration);
+            }
+            if batch.is_empty() {
+                break;
+            }
+            let node_label = Arc::clone(node_labels.entry(label.clone()).or_insert_with(|| {
+                Arc::new(self.node_batch_label(&label, &extra_set))
+            }));
+            
+            if self.options.edges_by_ordinal {
+                self.record_node_ordinals(&label, &mut batch);
+            }
+            
+            // Ordinals count every row, so oversized and duplicate rows are only dropped afterwards
+            self.drop_oversized_rows(&filename, &label, &mut batch, &mut batch_raw)?;
+            self.drop_duplicate_id_rows(&filename, &duplicate_lines, &mut batch, &mut batch_raw)?;
+            if batch.is_empty() {
+                continue;
+            }
+            
+            self.pace_throughput(batch.len()).await;
+            let batch_start_time = Instant::now();
+            
+            // Check if we should terminate before processing batch
+            if self.terminate_on_error.load(Ordering::Relaxed) {
+                return Err(anyhow!("Loading terminated due to previous critical errors"));
+            }
+            self.check_memory_limit(&filename, batch.len())?;
+            
+            // Build batch data as Cypher list literals
+            let mut batch_items = Vec::new();
+            let mut hook_rows = Vec::new();
+            let mut incoming = Vec::new();
+            
+            for (j, row) in batch.iter().enumerate() {
+                let empty_string = String::new();
+                let node_id = row.get("id").unwrap_or(&empty_string);
+                let mut properties = HashMap::new();
+                let mut appended = HashMap::new();
+                let raw = &batch_raw[j];
+                if self.options.column_stats {
+                    self.observe_column_stats(file_stats.entry(label.clone()).or_default(), row, raw);
+                }
+                
+                // Add all properties except id and labels (none in topology-only mode)
+                for (key, value) in Self::ordered_entries(row, &positions) {
+                    if !self.options.topology_only && !Self::is_node_key_column(key, &self.options) && !value.is_empty()
+                       && !self.options.vector_columns.contains_key(key) {
+                        let Some(value) = Self::property_value(value, raw.quoted_columns.contains(key), &self.options) else {
+                            continue;
+                        };
+                        if append_columns.contains(key) {
+                            appended.insert(key.clone(), value);
+                        } else if !self.is_omitted_default(key, &value, raw.quoted_columns.contains(key)) {
+                            properties.insert(key.clone(), value);
+                        }
+                    }
+                }
+                
+                // Debug: show properties for first few records
+                if batch_num == 0 && j < 3 {
+                    info!("    Record {}: id = {:?}, properties = {:?}", j + 1, node_id, properties);
+                }
+                
+                // Build Cypher map: {id: value, props: {key: val, ...}}
+                let quoted = &raw.quoted_columns;
+                let id_literal = Self::row_id_literal(row, raw, "id");
+                let props_map = Self::build_cypher_map(&properties, quoted, &positions, &self.options, column_types);
+                if self.options.checksum {
+                    let ids = [Self::canonical_csv_value(node_id, quoted.contains("id"))];
+                    file_checksums.entry(label.clone()).or_default()
+                        .add(&Self::entity_fingerprint(&ids, &properties, quoted, &self.options));
+                }
+                let mut item = format!("{{id: {}, props: {}", id_literal, props_map);
+                if !append_columns.is_empty() {
+                    item.push_str(&format!(", append: {}", Self::build_cypher_map(&appended, quoted, &positions, &self.options, column_types)));
+                }
+                if has_vectors {
+                    item.push_str(&format!(", vectors: {{{}}}", self.vector_entries(row, &filename, raw)?.join(", ")));
+                }
+                item.push('}');
+                
+                if self.options.batch_hook.is_some() {
+                    let mut hook_row = serde_json::Map::new();
+                    hook_row.insert("id".to_string(), Self::typed_json_value(node_id, quoted.contains("id")));
+                    hook_row.insert("props".to_string(), Self::json_map(&properties, quoted, &self.options, column_types));
+                    if !append_columns.is_empty() {
+                        hook_row.insert("append".to_string(), Self::json_map(&appended, quoted, &self.options, column_types));
+                    }
+                    if has_vectors {
+                        let vectors: serde_json::Map<_, _> = self.vector_values(row, &filename, raw)?.into_iter()
+                            .map(|(column, elements)| (column, serde_json::json!(elements)))
+                            .collect();
+                        hook_row.insert("vectors".to_string(), vectors.into());
+                    }
+                    hook_rows.push(serde_json::Value::Object(hook_row));
+                }
+                batch_items.push(item);
+                // CREATE mode never overwrites, so only merges and property updates can conflict
+                if self.options.detect_conflicts && (self.merge_mode || self.options.properties_only) {
+                    incoming.push((raw, row.get("id").map_or("", String::as_str), properties));
+                }
+            }
+            
+            if let Some(hook) = &self.options.batch_hook {
+                batch_items = Self::apply_batch_hook(hook, BatchKind::Nodes(&label), hook_rows);
+                if batch_items.is_empty() {
+                    continue;
+                }
+            }
+            
+            // Build complete UNWIND query with inline batch data
+            let batch_literal = format!("[{}]", batch_items.join(", "));
+            
+            let mut unwind_query = if self.options.properties_only {
+                // Only update existing nodes; returned ids tell us which rows matched
+                format!(
+                    "UNWIND {} AS row MATCH (n:{} {{id: row.id}}) SET n += row.props{} RETURN row.id",
+                    batch_literal, node_label.label_ident, node_label.extra_set
+                )
+            } else if self.merge_mode {
+                format!(
+                    "UNWIND {} AS row MERGE (n:{} {{id: row.id}}) SET n += row.props{}",
+                    batch_literal, node_label.label_ident, node_label.extra_set
+                )
+            } else {
+                format!(
+                    "UNWIND {} AS row CREATE (n:{}) SET n.id = row.id, n += row.props{}",
+                    batch_literal, node_label.label_ident, node_label.extra_set
+                )
+            };
+            
+            // Debug: show generated query for first batch
+            if batch_num == 0 {
+                info!("    Generated UNWIND query template");
+                info!("    Batch size: {} nodes", batch_items.len());
+                if !batch_items.is_empty() {
+                    info!("    First item example: {}", batch_items[0]);
+                }
+            }
+            
+            // Skip batches already committed by an earlier attempt and mark this one atomically.
+            // A batch whose fallback was interrupted resumes the fallback instead.
+            let markers = if self.idempotent_batches_enabled() {
+                Some(self.batch_markers(Self::batch_key(&filename, batch_num, batch_size)).await?)
+            } else {
+                None
+            };
+            if let Some(markers) = &markers {
+                if markers.committed {
+                    info!("⏭️ Batch {} of {} already committed, skipping", batch_num + 1, filename);
+                    total_loaded += batch.len();
+                    continue;
+                }
+                if markers.loaded_rows.is_empty() {
+                    unwind_query.push_str(&Self::batch_marker_clause(&markers.key));
+                }
+            }
+            
+            if !incoming.is_empty() {
+                self.report_node_conflicts(&filename, &node_label.label_ident, &incoming).await?;
+            }
+            
+            // Execute UNWIND query with inline batch data; up to --concurrency batches are in flight
+            let rows = batch.len();
+            in_flight.push(async move {
+                let loaded = self.execute_node_batch(file, &node_label, batch_num, (batch, batch_raw), unwind_query, markers).await?;
+                Ok::<_, anyhow::Error>((batch_num, rows, loaded, batch_start_time))
+            });
+        }
+        
+        if let Some(bar) = file_bar {
+            bar.finish_and_clear();
+        }
+        for (label, stats) in file_stats {
+            self.merge_column_stats(&label, stats);
+        }
+        for (label, checksum) in file_checksums {
+            self.checksums.lock().unwrap_or_else(|e| e.into_inner())
+                .entry((false, label)).or_default().merge(&checksum);
+        }
+        self.close_dead_letters()?;
+        self.metrics.rows_loaded.fetch_add(total_loaded, Ordering::Relaxed);
+        let duration = start_time.elapsed();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] ✅ Loaded {} {} nodes (Duration: {:?})", 
+              timestamp, total_loaded, name, duration);
+        
+        Ok(())
+    }
+    
+    /// Send one prepared batch of node rows, falling back to per-row queries when the batch
+    /// query fails. A batch with fallback rows already committed by an earlier attempt resumes
+    /// the fallback instead of sending the batch query. Returns the number of nodes loaded.
+    async fn execute_node_batch(&self, file: &NodeFileBatches<'_>, node_label: &NodeBatchLabel, batch_num: usize,
+                                (batch, raw_records): (CsvRows, Vec<RawCsvRecord>), unwind_query: String,
+                                markers: Option<BatchMarkers>) -> Result<usize> {
+        if self.options.dry_run {
+            self.log_dry_run_query(&self.graph_name, &unwind_query);
+            return Ok(batch.len());
+        }
+        let batch_key = markers.as_ref().map(|markers| markers.key.as_str());
+        let loaded_rows = markers.as_ref().map(|markers| &markers.loaded_rows).filter(|rows| !rows.is_empty());
+        let batch_result = match loaded_rows {
+            // Some rows are in already, so the batch query would create them a second time
+            Some(rows) => {
+                info!("⏭️ Resuming the fallback of batch {} of {}, {} rows already loaded", batch_num + 1, file.filename, rows.len());
+                Err(None)
+            }
+            None => self.send_query(false, &self.graph_name, &unwind_query, batch_key).await.map_err(Some),
+        };
+        match batch_result {
+            Ok(result) if self.options.properties_only => {
+                // Compare ids as the literals they were sent as, so `1.0` (sent as `1`) matches
+                let matched: HashSet<String> = result.into_iter()
+                    .filter_map(|row| row.first().map(Self::falkor_value_literal))
+                    .collect();
+                let mut missing = Vec::new();
+                for (row, raw) in batch.iter().zip(&raw_records) {
+                    let Some(id) = row.get("id") else {
+                        continue;
+                    };
+                    if !matched.contains(&Self::typed_cypher_literal(id, raw.quoted_columns.contains("id"))) {
+                        self.report_rejected_row(file.filename, raw, TARGET_NOT_FOUND)?;
+                        self.write_dead_letter(file.filename, raw, TARGET_NOT_FOUND)?;
+                        missing.push(id.as_str());
+                    }
+                }
+                if !missing.is_empty() {
+                    warn!("⚠️ {} {} nodes not found for property update, rejected ids: {:?}",
+                          missing.len(), node_label.label, missing);
+                }
+                self.record_query_success();
+                Ok(batch.len() - missing.len())
+            }
+            Ok(_) => {
+                self.record_query_success();
+                Ok(batch.len())
+            }
+            Err(e) => {
+                if let Some(e) = e {
+                    error!("❌ Error loading batch with UNWIND: {}", e);
+                    
+                    // FalkorDB rolls back every write of a query that fails on the server, so a
+                    // batch never commits partially. Only a client-side failure (timeout, dropped
+                    // connection) can hide a batch that fully committed, which the marker reveals.
+                    if let Some(key) = batch_key {
+                        if self.batch_committed(key).await.unwrap_or(false) {
+                            warn!("⚠️ Batch {} of {} committed despite the error, not retrying it", batch_num + 1, file.filename);
+                            return Ok(batch.len());
+                        }
+                    }
+                    if self.options.fail_fast {
+                        self.terminate_on_error.store(true, Ordering::Relaxed);
+                        return Err(anyhow!("Batch {} of {} failed (--fail-fast): {}", batch_num + 1, file.filename, e));
+                    }
+                    self.check_batch_connection_error(&e, batch_num, file.filename)?;
+                    self.record_query_failure(file.filename)?;
+                    
+                    error!("Falling back to individual queries for this batch...");
+                }
+                
+                // Fallback to individual queries if batch fails
+                let mut successful_nodes = 0;
+                for (i, row) in batch.iter().enumerate() {
+                    if loaded_rows.is_some_and(|rows| rows.contains(&i)) {
+                        successful_nodes += 1;
+                        continue;
+                    }
+                    let raw = &raw_records[i];
+                    let empty_string = String::new();
+                    let node_id = row.get("id").unwrap_or(&empty_string);
+                    let mut properties = Vec::new();
+                    
+                    properties.extend(self.vector_entries(row, file.filename, raw)?);
+                    properties.extend(file.source_stamp.iter().map(|(property, value)| format!("{}: {}", property, value)));
+                    let (column_properties, append_updates) = self.fallback_properties("n", row, raw, file.positions, file.column_types, |key| {
+                        Self::is_node_key_column(key, &self.options)
+                            || node_label.label_property.as_ref().is_some_and(|(property, _)| property == key)
+                    });
+                    properties.extend(column_properties);
+                    properties.extend(node_label.label_property.iter().map(|(property, value)| format!("{}: {}", property, value)));
+                    
+                    let id_str = Self::row_id_literal(row, raw, "id");
+                    
+                    let mut node_query = if self.options.properties_only {
+                        format!("MATCH (n:{} {{id: {}}}) SET n += {{{}}} RETURN n.id",
+                                node_label.label_ident, id_str, properties.join(", "))
+                    } else if self.merge_mode {
+                        if properties.is_empty() {
+                            format!("MERGE (:{} {{id: {}}})", node_label.label_ident, id_str)
+                        } else {
+                            format!("MERGE (:{} {{id: {}, {}}})", node_label.label_ident, id_str, properties.join(", "))
+                        }
+                    } else if properties.is_empty() {
+                        format!("CREATE (:{} {{id: {}}})", node_label.label_ident, id_str)
+                    } else {
+                        format!("CREATE (:{} {{id: {}, {}}})", node_label.label_ident, id_str, properties.join(", "))
+                    };
+                    // Each row records its own marker, so a rerun skips just the rows that made it
+                    if let Some(key) = batch_key {
+                        node_query.push_str(&Self::batch_marker_clause(&Self::row_marker_key(key, i)));
+                    }
+                    
+                    match self.execute_graph_query_count(&node_query).await {
+                        Ok(0) if self.options.properties_only => {
+                            warn!("⚠️ {} node {:?} not found for property update, rejected", node_label.label, node_id);
+                            self.report_rejected_row(file.filename, raw, TARGET_NOT_FOUND)?;
+                            self.write_dead_letter(file.filename, raw, TARGET_NOT_FOUND)?;
+                        }
+                        Ok(_) if !append_updates.is_empty() => {
+                            let append_query = format!("MATCH (n:{} {{id: {}}}) SET {}",
+                                                       node_label.label_ident, id_str, append_updates.join(", "));
+                            match self.execute_graph_query(&append_query).await {
+                                Ok(_) => {
+                                    successful_nodes += 1;
+                                    self.record_query_success();
+                                }
+                                Err(e2) => {
+                                    error!("❌ Error appending list properties: {}", e2);
+                                    error!("Query: {}", append_query);
+                                    self.report_rejected_row(file.filename, raw, &e2.to_string())?;
+                                    self.write_dead_letter(file.filename, raw, &e2.to_string())?;
+                                    self.record_query_failure(file.filename)?;
+                                }
+                            }
+                        }
+                        Ok(_) => {
+                            successful_nodes += 1;
+                            self.record_query_success();
+                        }
+                        Err(e2) => {
+                            error!("❌ Error loading node: {}", e2);
+                            error!("Query: {}", node_query);
+                            self.report_rejected_row(file.filename, raw, &e2.to_string())?;
+                            self.write_dead_letter(file.filename, raw, &e2.to_string())?;
+                            self.record_query_failure(file.filename)?;
+                        }
+                    }
+                }
+                
+                if successful_nodes != batch.len() {
+                    warn!("⚠️ Loaded {} out of {} nodes in this batch", successful_nodes, batch.len());
+                }
+                Ok(successful_nodes)
+            }
+        }
+    }
+}
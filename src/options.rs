@@ -0,0 +1,395 @@
+//! Options that configure a load and the value types they take.
+
+use indicatif::MultiProgress;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::sync::Arc;
+
+use crate::loader::FalkorDBCSVLoader;
+
+/// How label and relationship type names are made safe for Cypher
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum IdentifierStyle {
+    /// Replace `:` in file-name labels with `_` (changes the label)
+    #[default]
+    Sanitize,
+    /// Keep names as-is and wrap them in backticks in generated queries
+    Backtick,
+}
+
+/// Effect of an explicit null (see `--null-value`) on an existing property
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum NullHandling {
+    /// Set the property to null, which removes it (FalkorDB does not store null properties)
+    #[default]
+    Remove,
+    /// Leave out the property so an existing value is kept
+    Keep,
+}
+
+/// Type of a column set in a `--schema` file, used instead of inferring it from each value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    /// Always a string, even if the value looks like a number
+    String,
+    /// 64-bit integer
+    Int,
+    /// Float, also for whole numbers
+    Float,
+    /// `true` or `false` in any case
+    Bool,
+    /// ISO-8601 date or datetime, like `--infer-dates`
+    Date,
+    /// List split on the column's `--array-column` separator, or `|`
+    List,
+}
+
+impl ColumnType {
+    /// Name as written in the schema file
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ColumnType::String => "string",
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Bool => "bool",
+            ColumnType::Date => "date",
+            ColumnType::List => "list",
+        }
+    }
+    
+    /// Whether `value` is valid for the type; a list or string takes anything
+    pub(crate) fn accepts(self, value: &str) -> bool {
+        match self {
+            ColumnType::String | ColumnType::List => true,
+            ColumnType::Int => value.parse::<i64>().is_ok(),
+            ColumnType::Float => value.parse::<f64>().is_ok(),
+            ColumnType::Bool => FalkorDBCSVLoader::parse_bool(value).is_some(),
+            ColumnType::Date => FalkorDBCSVLoader::parse_temporal(value).is_some(),
+        }
+    }
+    
+    /// Whether `--verify-sample` and `--checksum` can compare the stored value with the CSV
+    pub(crate) fn is_comparable(self) -> bool {
+        matches!(self, ColumnType::String | ColumnType::Int)
+    }
+}
+
+/// Casing convention applied to label and relationship type names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LabelCase {
+    /// Keep names as they appear in the source
+    #[default]
+    None,
+    /// Uppercase the first character, keep the rest (`person` -> `Person`)
+    UpperFirst,
+    /// All lowercase
+    Lower,
+    /// All uppercase
+    Upper,
+}
+
+impl LabelCase {
+    /// Apply the convention to each `:`-separated part of a name
+    pub(crate) fn apply(self, name: &str) -> String {
+        name.split(':')
+            .map(|part| match self {
+                LabelCase::None => part.to_string(),
+                LabelCase::Lower => part.to_lowercase(),
+                LabelCase::Upper => part.to_uppercase(),
+                LabelCase::UpperFirst => {
+                    let mut chars = part.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars).collect(),
+                        None => String::new(),
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+}
+
+/// Which CSV values have leading and trailing whitespace removed when files are read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TrimValues {
+    /// Keep every value exactly as in the file
+    None,
+    /// Trim property values but keep id columns as-is
+    Properties,
+    /// Trim only the `id`, `source` and `target` columns used for matching
+    #[default]
+    Ids,
+    /// Trim every value
+    All,
+}
+
+impl TrimValues {
+    /// Trim the affected values of a parsed row in place
+    pub(crate) fn apply(self, row: &mut HashMap<String, String>) {
+        for (column, value) in row.iter_mut() {
+            let is_id = matches!(column.as_str(), "id" | "source" | "target");
+            let trim = match self {
+                TrimValues::None => false,
+                TrimValues::Properties => !is_id,
+                TrimValues::Ids => is_id,
+                TrimValues::All => true,
+            };
+            if trim && value.trim().len() != value.len() {
+                *value = value.trim().to_string();
+            }
+        }
+    }
+}
+
+/// Order in which node files, and then edge files, are loaded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FileOrder {
+    /// By file name, so part files of a sharded export load in order
+    #[default]
+    Name,
+    /// Largest files first
+    SizeDesc,
+    /// Smallest files first
+    SizeAsc,
+}
+
+/// Field delimiter of the CSV data files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delimiter(pub u8);
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Delimiter(b',')
+    }
+}
+
+/// What `--on-duplicate-id` does with a node id already seen for the same labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnDuplicateId {
+    /// Log the duplicates and load every row
+    Warn,
+    /// Fail the file before loading any of it
+    Error,
+    /// Load only the first row of each id and reject the others
+    Skip,
+}
+
+/// Output format of `--plan`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PlanFormat {
+    /// Numbered phases for reading
+    #[default]
+    Text,
+    /// One JSON document for tooling
+    Json,
+}
+
+/// Output format of `--stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatsFormat {
+    /// One line per label and relationship type
+    #[default]
+    Text,
+    /// One JSON document for dashboards
+    Json,
+}
+
+/// Which kind of batch a [`BatchHook`] is called for, with the label or relationship type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchKind<'a> {
+    Nodes(&'a str),
+    Edges(&'a str),
+}
+
+/// Callback that can filter, enrich or rewrite the rows of every batch before its UNWIND query
+/// is built. Rows are JSON objects with the same fields the query reads (`id` or
+/// `source_id`/`target_id`, `props`, and `append`, `weight` and `vectors` when those options are
+/// used), with values already typed as they would be loaded.
+#[derive(Clone)]
+pub struct BatchHook(pub Arc<BatchHookFn>);
+
+/// Signature of a [`BatchHook`] callback
+pub type BatchHookFn = dyn Fn(BatchKind<'_>, &mut Vec<serde_json::Value>) + Send + Sync;
+
+impl std::fmt::Debug for BatchHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BatchHook")
+    }
+}
+
+/// Optional loading behaviours layered on top of the core connection settings
+#[derive(Debug, Clone, Default)]
+pub struct LoaderOptions {
+    /// Skip all non-key properties so only the graph topology is loaded
+    pub topology_only: bool,
+    /// Update properties of existing nodes/edges only, reporting rows whose target is missing
+    pub properties_only: bool,
+    /// Run extra consistency checks before loading
+    pub strict: bool,
+    /// CSV file receiving edge endpoint ids that matched no node
+    pub unmatched_edges_output: Option<PathBuf>,
+    /// Quoting style for label and relationship type names
+    pub identifier_style: IdentifierStyle,
+    /// Columns whose values are appended to existing list properties
+    pub append_list_columns: Vec<String>,
+    /// CSV file receiving rows that failed to load
+    pub rejects_output: Option<PathBuf>,
+    /// Guard each target graph with an advisory loader lock node
+    pub lock: bool,
+    /// Take over an existing loader lock regardless of its age
+    pub force_lock: bool,
+    /// Edge column stored as a float relationship weight
+    pub weight_column: Option<String>,
+    /// Number of slowest batches to report after loading (0 = disabled)
+    pub report_slowest_batches: usize,
+    /// Treat fields that were quoted in the source CSV as strings regardless of content
+    pub quoted_as_string: bool,
+    /// Columns loaded as `vecf32` vector properties, with their expected dimension
+    pub vector_columns: HashMap<String, usize>,
+    /// Maximum number of distinct node labels allowed in a load
+    pub max_labels: Option<usize>,
+    /// Maximum number of distinct relationship types allowed in a load
+    pub max_rel_types: Option<usize>,
+    /// Prometheus pushgateway base URL for load metrics
+    #[cfg(feature = "pushgateway")]
+    pub pushgateway_url: Option<String>,
+    /// Job name for pushed metrics
+    #[cfg(feature = "pushgateway")]
+    pub job_name: String,
+    /// Push metrics after every loaded file, not only at the end
+    #[cfg(feature = "pushgateway")]
+    pub push_each_file: bool,
+    /// Casing convention for labels and relationship types
+    pub label_case: LabelCase,
+    /// Resolve edge endpoints as node ordinals instead of ids
+    pub edges_by_ordinal: bool,
+    /// Order generated property maps by CSV column position rather than by name
+    pub preserve_insertion_order: bool,
+    /// CREATE relationships even in merge mode
+    pub no_merge_relationships: bool,
+    /// Comment tag prepended to every query
+    pub query_tag: Option<String>,
+    /// Treat node/edge files without data rows as an error
+    pub fail_on_empty_file: bool,
+    /// Mark committed CREATE-mode batches so retries and reruns skip them
+    pub idempotent_batches: bool,
+    /// Rows per file to read back and compare with the source after loading
+    pub verify_sample: Option<usize>,
+    /// CSV values treated as explicit nulls
+    pub null_values: Vec<String>,
+    /// Whether explicit nulls remove existing properties or leave them untouched
+    pub null_handling: NullHandling,
+    /// Progress bar display shared with the logger; `None` reports progress as log lines
+    pub progress_bars: Option<MultiProgress>,
+    /// Edge column giving the per-row relationship direction
+    pub direction_column: Option<String>,
+    /// Direction column values meaning source -> target
+    pub forward_values: Vec<String>,
+    /// Direction column values meaning target -> source
+    pub reverse_values: Vec<String>,
+    /// Propose fixes when label validation fails
+    pub suggest_fixes: bool,
+    /// Collect and report per-column statistics
+    pub column_stats: bool,
+    /// Compare checksums of the sent and stored data after loading
+    pub checksum: bool,
+    /// Namespace prefix for node labels
+    pub label_prefix: Option<String>,
+    /// Namespace prefix for relationship types
+    pub rel_type_prefix: Option<String>,
+    /// Wait up to this long for indexes to finish populating before loading edges
+    pub wait_for_indexes: Option<Duration>,
+    /// Property that records where each node and relationship came from
+    pub stamp_source: Option<String>,
+    /// Value stored in the `stamp_source` property instead of the file name
+    pub source_tag: Option<String>,
+    /// Per-column default values that are implied rather than stored
+    pub omit_defaults: HashMap<String, String>,
+    /// Times a query is re-sent after a connection error during the load
+    pub reconnect_retries: u32,
+    /// Delay before the first mid-load retry, doubled for each further retry
+    pub reconnect_backoff: Duration,
+    /// Largest estimated row size loaded; bigger rows are rejected
+    pub max_row_bytes: Option<usize>,
+    /// Compare incoming node properties with the stored ones before overwriting
+    pub detect_conflicts: bool,
+    /// CSV file receiving the detected conflicts
+    pub conflicts_output: Option<PathBuf>,
+    /// Embedder callback run on every node and edge batch (not settable from the command line)
+    pub batch_hook: Option<BatchHook>,
+    /// Node properties matching the source and target of each relationship type, instead of `id`
+    pub endpoint_keys: HashMap<String, (String, String)>,
+    /// Rows per second batches are paced to
+    pub throughput_target: Option<u64>,
+    /// Directory receiving a copy of every executed query
+    pub capture_bundle: Option<PathBuf>,
+    /// Size limit of the captured query files in bytes
+    pub capture_max_bytes: u64,
+    /// Template deriving each edge's relationship type from `{column}` placeholders
+    pub rel_type_template: Option<String>,
+    /// Resident memory in bytes above which the load is aborted
+    pub max_rss_bytes: Option<u64>,
+    /// Which CSV values have surrounding whitespace trimmed
+    pub trim_values: TrimValues,
+    /// Property that additionally stores each node's label
+    pub label_as_property: Option<String>,
+    /// Skip the automatic `id` index per node label
+    pub skip_id_index: bool,
+    /// Consecutive edge batches without loadable rows after which the file fails
+    pub max_empty_batches: Option<usize>,
+    /// Queries to run after each label's node files have loaded, keyed by label
+    pub post_load_queries: HashMap<String, Vec<String>>,
+    /// Order in which node and edge files are loaded
+    pub file_order: FileOrder,
+    /// Directory of per-source-file rejects in the source format
+    pub rejects_dir: Option<PathBuf>,
+    /// Abort before the edge phase when an index needed for endpoint matching is missing
+    pub require_indexes: bool,
+    /// Field delimiter of the CSV files (indexes.csv and constraints.csv included)
+    pub delimiter: Delimiter,
+    /// Node batches of a file in flight at once (0 and 1 both send one at a time)
+    pub concurrency: usize,
+    /// Stop the load at the first failed query instead of falling back to per-row queries
+    pub fail_fast: bool,
+    /// Failed batch and row queries in a row after which the load stops (0 = no limit)
+    pub max_consecutive_failures: usize,
+    /// Directory of `failed_nodes_<label>.csv` / `failed_edges_<type>.csv` files for rows whose query failed
+    pub dead_letter_dir: Option<PathBuf>,
+    /// Log every query instead of sending it
+    pub dry_run: bool,
+    /// Load unquoted `true`/`false` property values as booleans
+    pub infer_bools: bool,
+    /// Load unquoted ISO-8601 property values with `date()` / `localdatetime()`
+    pub infer_dates: bool,
+    /// Columns loaded as list properties, with the separator between their elements
+    pub array_columns: HashMap<String, String>,
+    /// `--schema` column types per label or relationship type as named by the CSV file
+    pub schema: HashMap<String, HashMap<String, ColumnType>>,
+    /// Column giving each node row its labels instead of the file name
+    pub label_column: Option<String>,
+    /// Column giving each edge row its relationship type instead of the file name
+    pub rel_type_column: Option<String>,
+    /// Delete the contents of each target graph before loading into it
+    pub clear_graph: bool,
+    /// Duplicate node id check before each node file is loaded
+    pub on_duplicate_id: Option<OnDuplicateId>,
+    /// Check edge endpoints against the graph before each batch
+    pub verify_endpoints: bool,
+    /// Compare CSV row counts with graph counts after loading
+    pub verify: bool,
+    /// Count difference `verify` tolerates per label or relationship type
+    pub verify_tolerance: usize,
+    /// Skip the edge phase
+    pub nodes_only: bool,
+    /// Skip the node phase
+    pub edges_only: bool,
+    /// Skip index and constraint creation
+    pub skip_schema: bool,
+    /// Label / relationship type patterns of the files to load (all when empty)
+    pub include_labels: Vec<String>,
+    /// Label / relationship type patterns of the files to skip
+    pub exclude_labels: Vec<String>,
+}
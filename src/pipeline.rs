@@ -0,0 +1,683 @@
+//! Loading a whole CSV directory or a directory of graphs.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use falkordb::FalkorValue;
+use log::{error, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use std::sync::atomic::Ordering;
+
+use crate::loader::FalkorDBCSVLoader;
+use crate::rejects::DeadLetters;
+
+/// Age after which an advisory loader lock is considered stale and may be taken over
+const LOADER_LOCK_STALE_SECS: i64 = 12 * 60 * 60;
+
+impl FalkorDBCSVLoader {
+    /// Count total records across all CSV files for progress tracking
+    pub(crate) fn count_total_records(&self, files: &[std::path::PathBuf]) -> Result<usize> {
+        Ok(files.iter().map(|file_path| self.file_record_count(file_path)).sum())
+    }
+    
+    /// Data rows of a CSV file, counted on first use and remembered for the rest of the load
+    /// so that each file is only read once for progress reporting
+    pub(crate) fn file_record_count(&self, file_path: &Path) -> usize {
+        let mut counts = self.record_counts.lock().unwrap_or_else(|e| e.into_inner());
+        *counts.entry(file_path.to_path_buf()).or_insert_with(|| {
+            Self::csv_reader(file_path, self.options.delimiter.0)
+                .map(|mut rdr| rdr.records().count())
+                .unwrap_or(0)
+        })
+    }
+    
+    /// Fail when the load would create more distinct labels or relationship types than
+    /// `--max-labels` / `--max-rel-types` allow, e.g. because a label column was mis-parsed
+    fn check_schema_cardinality(&self, node_files: &[PathBuf], edge_files: &[PathBuf]) -> Result<()> {
+        info!("🔍 Checking label and relationship type cardinality...");
+        
+        let mut labels = BTreeSet::new();
+        let mut rel_types = BTreeSet::new();
+        
+        for file_path in node_files {
+            labels.extend(self.node_file_labels(file_path)?);
+        }
+        
+        for file_path in edge_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+            if let Some(rel_type) = Self::entity_name_from_file_name(&file_name, "edges_") {
+                rel_types.insert(rel_type.to_string());
+            }
+            
+            // Per-row endpoint labels are the values that can explode, so scan every row
+            if self.options.max_labels.is_some() {
+                let mut rdr = Self::csv_reader(file_path, self.options.delimiter.0)?;
+                for result in rdr.deserialize::<HashMap<String, String>>() {
+                    let record = result?;
+                    for column in ["source_label", "target_label"] {
+                        if let Some(label) = record.get(column).map(|l| l.trim()).filter(|l| !l.is_empty()) {
+                            let label = self.label_mapping.get(label).map_or(label, |l| l.as_str());
+                            if !labels.contains(label) {
+                                labels.insert(label.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        let mut exceeded = Vec::new();
+        for (kind, values, limit) in [
+            ("labels", &labels, self.options.max_labels),
+            ("relationship types", &rel_types, self.options.max_rel_types),
+        ] {
+            if let Some(limit) = limit.filter(|limit| values.len() > *limit) {
+                let sample: Vec<&String> = values.iter().take(20).collect();
+                error!("❌ Found {} distinct {} (limit {}), first values: {:?}",
+                       values.len(), kind, limit, sample);
+                exceeded.push(format!("{} {} > {}", values.len(), kind, limit));
+            }
+        }
+        
+        if !exceeded.is_empty() {
+            return Err(anyhow!("Schema cardinality check failed: {}", exceeded.join(", ")));
+        }
+        
+        info!("✅ {} labels and {} relationship types within limits", labels.len(), rel_types.len());
+        Ok(())
+    }
+    
+    /// Warn when node files that load into the same label have differing column sets
+    fn check_node_column_consistency(&self, node_files: &[PathBuf]) -> Result<()> {
+        info!("🔍 Checking column consistency across node files...");
+        
+        // Group header sets by derived label
+        let mut headers_by_label: BTreeMap<String, Vec<(PathBuf, BTreeSet<String>)>> = BTreeMap::new();
+        for file_path in node_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let Some(raw_label) = Self::entity_name_from_file_name(&file_name, "nodes_") else {
+                continue;
+            };
+            let mut rdr = Self::csv_reader(file_path, self.options.delimiter.0)?;
+            let headers = rdr.headers()?.iter().map(|h| h.to_string()).collect();
+            headers_by_label
+                .entry(self.label_name(raw_label))
+                .or_default()
+                .push((file_path.clone(), headers));
+        }
+        
+        let mut inconsistent_labels = 0;
+        for (label, files) in &headers_by_label {
+            if files.len() < 2 {
+                continue;
+            }
+            let all_columns: BTreeSet<&String> = files.iter().flat_map(|(_, h)| h).collect();
+            if files.iter().all(|(_, headers)| headers.len() == all_columns.len()) {
+                continue;
+            }
+            
+            inconsistent_labels += 1;
+            warn!("⚠️ Node files for label '{}' have inconsistent columns:", label);
+            for (file_path, headers) in files {
+                let missing: Vec<&String> = all_columns.iter()
+                    .filter(|c| !headers.contains(c.as_str()))
+                    .copied()
+                    .collect();
+                if !missing.is_empty() {
+                    warn!("   {:?} is missing columns {:?}", file_path.file_name().unwrap_or_default(), missing);
+                }
+            }
+        }
+        
+        if inconsistent_labels == 0 {
+            info!("✓ Column sets are consistent for all labels");
+        }
+        
+        Ok(())
+    }
+    
+    /// Check for potential crash causes and system resource issues
+    async fn check_system_health(&self) -> Result<()> {
+        info!("🔍 Checking system health before loading...");
+        
+        // Test basic connectivity
+        match self.execute_graph_query("RETURN 1 as test").await {
+            Ok(_) => info!("✓ FalkorDB connectivity: OK"),
+            Err(e) => {
+                error!("❌ FalkorDB connectivity test failed: {}", e);
+                return Err(anyhow!("FalkorDB is not responsive: {}", e));
+            }
+        }
+        
+        // Test memory allocation with a small query
+        let test_query = "CREATE (test:TestNode {id: 'health_check', timestamp: timestamp()}) RETURN test";
+        match self.execute_graph_query(test_query).await {
+            Ok(_) => {
+                info!("✓ FalkorDB memory allocation: OK");
+                // Clean up test node
+                let _ = self.execute_graph_query("MATCH (test:TestNode {id: 'health_check'}) DELETE test").await;
+            }
+            Err(e) => {
+                warn!("⚠️ FalkorDB may have memory issues: {}", e);
+            }
+        }
+        
+        // Warn about large batch sizes in merge mode
+        if self.merge_mode {
+            warn!("⚠️ Running in MERGE mode - this generates complex queries that may strain FalkorDB");
+            warn!("   Consider using smaller batch sizes or CREATE mode for initial loads");
+        }
+        
+        Ok(())
+    }
+    
+    /// Load all CSV files from the csv_output directory
+    pub async fn load_all_csvs(&mut self, batch_size: usize) -> Result<()> {
+        if !self.csv_dir.exists() {
+            return Err(anyhow!("Directory {:?} does not exist", self.csv_dir));
+        }
+        
+        // Start a fresh unmatched-edges report for this run
+        if let Some(output_path) = &self.options.unmatched_edges_output {
+            let mut writer = csv::Writer::from_path(output_path)?;
+            writer.write_record(["file", "label", "id"])?;
+            writer.flush()?;
+        }
+        
+        // Files may have changed since a previous run, so count their rows again
+        self.record_counts.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        
+        // Start a fresh rejects file for this run
+        if let Some(output_path) = &self.options.rejects_output {
+            let mut writer = csv::Writer::from_path(output_path)?;
+            writer.write_record(["file", "line", "error", "record"])?;
+            writer.flush()?;
+        }
+        
+        if self.options.max_rss_bytes.is_some() && Self::resident_set_bytes().is_none() {
+            warn!("⚠️ --max-rss-mb is not supported on this platform (no /proc/self/status), memory is not monitored");
+        }
+        
+        // Start a fresh capture bundle for this run
+        if let Some(dir) = &self.options.capture_bundle {
+            std::fs::create_dir_all(dir)?;
+            File::create(dir.join("manifest.jsonl"))?;
+            info!("📼 Capturing executed queries to {:?}", dir);
+        }
+        
+        // Reject files are started on first use; older ones in the directory would be reloaded too
+        if let Some(dir) = &self.options.rejects_dir {
+            self.reject_file_headers.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+            if Self::list_csv_dir(dir).is_ok_and(|files| files.iter().any(|f| f.extension().is_some_and(|e| e == "csv"))) {
+                warn!("⚠️ --rejects-dir {:?} already contains CSV files; files not rewritten by this run are left as they are", dir);
+            }
+        }
+        *self.dead_letters.get_mut().unwrap_or_else(|e| e.into_inner()) = DeadLetters::default();
+        
+        // Start a fresh conflicts report for this run
+        if let Some(output_path) = &self.options.conflicts_output {
+            let mut writer = csv::Writer::from_path(output_path)?;
+            writer.write_record(["file", "line", "id", "property", "existing", "incoming"])?;
+            writer.flush()?;
+        }
+        
+        // Check for multi-graph mode
+        if self.multi_graph_mode {
+            self.load_multi_graph_csvs(batch_size).await
+        } else {
+            self.load_single_graph_csvs(batch_size).await
+        }
+    }
+    
+    /// Load CSV files into a single graph, holding the advisory loader lock if enabled
+    async fn load_single_graph_csvs(&mut self, batch_size: usize) -> Result<()> {
+        // A dry run sends no queries, so there is no lock to take
+        if !self.options.lock || self.options.dry_run {
+            return self.load_single_graph_csvs_unlocked(batch_size).await;
+        }
+        
+        let token = self.acquire_loader_lock().await?;
+        let result = self.load_single_graph_csvs_unlocked(batch_size).await;
+        
+        // Release the lock even if loading failed; only a crashed process leaves it behind
+        if let Err(e) = self.release_loader_lock(&token).await {
+            warn!("⚠️ Could not release loader lock on graph '{}': {}", self.graph_name, e);
+        }
+        
+        result
+    }
+    
+    /// Take the advisory `(:_LoaderLock)` node of the current graph, returning its token.
+    /// Fails if another loader holds a lock younger than `LOADER_LOCK_STALE_SECS` unless forced.
+    async fn acquire_loader_lock(&self) -> Result<String> {
+        let pid = std::process::id();
+        let now = Utc::now();
+        let started_at = now.timestamp();
+        let token = format!("{}-{}", pid, now.timestamp_nanos_opt().unwrap_or(started_at));
+        
+        // MERGE so that two loaders starting together cannot both create a lock
+        let query = format!(
+            "MERGE (l:_LoaderLock) ON CREATE SET l.pid = {}, l.started_at = {}, l.token = '{}' \
+             RETURN l.token, l.pid, l.started_at",
+            pid, started_at, token
+        );
+        let rows = self.execute_graph_query(&query).await?;
+        let row = rows.first().ok_or_else(|| anyhow!("Loader lock query returned no rows"))?;
+        let held_token = row.first().map(Self::falkor_value_to_string).unwrap_or_default();
+        
+        if held_token != token {
+            let held_pid = row.get(1).map(Self::falkor_value_to_string).unwrap_or_default();
+            let held_since = row.get(2)
+                .map(Self::falkor_value_to_string)
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(0);
+            let age = started_at - held_since;
+            
+            if age < LOADER_LOCK_STALE_SECS && !self.options.force_lock {
+                return Err(anyhow!(
+                    "Graph '{}' is locked by another loader (pid {}, started {}s ago); use --force to override",
+                    self.graph_name, held_pid, age
+                ));
+            }
+            
+            warn!("⚠️ Taking over loader lock on graph '{}' held by pid {} since {}s ago",
+                  self.graph_name, held_pid, age);
+            let takeover = format!(
+                "MATCH (l:_LoaderLock) SET l.pid = {}, l.started_at = {}, l.token = '{}'",
+                pid, started_at, token
+            );
+            self.execute_graph_query(&takeover).await?;
+        }
+        
+        info!("🔒 Acquired loader lock on graph '{}'", self.graph_name);
+        Ok(token)
+    }
+    
+    /// Remove the advisory lock node if it is still the one this loader created
+    async fn release_loader_lock(&self, token: &str) -> Result<()> {
+        let query = format!("MATCH (l:_LoaderLock {{token: '{}'}}) DELETE l", token);
+        self.execute_graph_query(&query).await?;
+        info!("🔓 Released loader lock on graph '{}'", self.graph_name);
+        Ok(())
+    }
+    
+    /// `--clear-graph`: delete the current graph, logging what it held. With `--lock` the graph
+    /// has to stay for the lock node, so everything else in it is deleted instead.
+    pub(crate) async fn clear_graph(&self) -> Result<()> {
+        if self.options.dry_run {
+            info!("🧪 Dry run, not clearing graph '{}'", self.graph_name);
+            return Ok(());
+        }
+        let graphs = self.client().list_graphs().await
+            .map_err(|e| anyhow!("Could not list graphs to clear '{}': {:?}", self.graph_name, e))?;
+        if !graphs.contains(&self.graph_name) {
+            info!("🧹 Graph '{}' doesn't exist yet, nothing to clear", self.graph_name);
+            return Ok(());
+        }
+        
+        let count = |rows: Vec<Vec<FalkorValue>>| match rows.first().and_then(|row| row.first()) {
+            Some(FalkorValue::I64(count)) => *count,
+            _ => 0,
+        };
+        let nodes = count(self.execute_graph_query("MATCH (n) WHERE NOT n:_LoaderLock RETURN count(n)").await?);
+        let relationships = count(self.execute_graph_query("MATCH ()-[r]->() RETURN count(r)").await?);
+        info!("🧹 Clearing graph '{}': {} nodes and {} relationships existed before loading", self.graph_name, nodes, relationships);
+        
+        if self.options.lock {
+            self.execute_graph_query("MATCH (n) WHERE NOT n:_LoaderLock DETACH DELETE n").await?;
+        } else {
+            self.client().select_graph(&self.graph_name).delete().await
+                .map_err(|e| anyhow!("Could not delete graph '{}': {:?}", self.graph_name, e))?;
+        }
+        Ok(())
+    }
+    
+    /// Load CSV files into a single graph
+    async fn load_single_graph_csvs_unlocked(&mut self, batch_size: usize) -> Result<()> {
+        // Batch timings are reported per graph
+        self.slowest_batches.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        // Node ordinals only refer to the graph being loaded
+        self.node_ids_by_ordinal.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        self.column_stats.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        self.checksums.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        self.seen_node_ids.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        
+        // Validate label consistency first
+        let label_mapping = self.validate_label_consistency()?;
+        self.label_mapping = label_mapping;
+        
+        let csv_files = self.list_selected_csv_files()?;
+        let mut node_files = Vec::new();
+        let mut edge_files = Vec::new();
+        
+        for file_path in csv_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            
+            if self.is_node_file(&file_name) {
+                node_files.push(file_path);
+            } else if self.is_edge_file(&file_name) {
+                edge_files.push(file_path);
+            }
+        }
+        
+        // Name order keeps part files of a sharded export in order
+        Self::order_files(&mut node_files, self.options.file_order)?;
+        Self::order_files(&mut edge_files, self.options.file_order)?;
+        
+        info!("Found {} node files and {} edge files", node_files.len(), edge_files.len());
+        // Node files are still needed for their labels with --edges-only; only loading skips them
+        let (loaded_node_files, loaded_edge_files): (&[PathBuf], &[PathBuf]) = if self.options.edges_only {
+            info!("⏭️ Skipping node files (--edges-only)");
+            (&[], &edge_files)
+        } else if self.options.nodes_only {
+            info!("⏭️ Skipping edge files (--nodes-only)");
+            (&node_files, &[])
+        } else {
+            (&node_files, &edge_files)
+        };
+        
+        // Labels of each node file, which with --label-column takes a pass over the column
+        let file_labels = node_files.iter()
+            .map(|path| self.node_file_labels(path))
+            .collect::<Result<Vec<_>>>()?;
+        let node_labels: HashSet<String> = file_labels.iter().flatten().cloned().collect();
+        for label in self.options.post_load_queries.keys().filter(|label| !node_labels.contains(*label)) {
+            warn!("⚠️ --post-load-queries has queries for label '{}', which has no node file; they won't run", label);
+        }
+        
+        if self.options.strict {
+            self.check_node_column_consistency(&node_files)?;
+        }
+        
+        if self.options.max_labels.is_some() || self.options.max_rel_types.is_some() {
+            self.check_schema_cardinality(&node_files, &edge_files)?;
+        }
+        
+        // Count total records for progress tracking if enabled
+        let (total_node_records, total_edge_records) = if self.progress_interval > 0 || self.options.progress_bars.is_some() {
+            let node_count = self.count_total_records(loaded_node_files).unwrap_or(0);
+            let edge_count = self.count_total_records(loaded_edge_files).unwrap_or(0);
+            info!("📊 Total records to process: {} nodes, {} edges", node_count, edge_count);
+            (node_count, edge_count)
+        } else {
+            (0, 0)
+        };
+        
+        self.overall_bar = self.progress_bar("total", total_node_records + total_edge_records);
+        
+        // Check system health first
+        self.check_system_health().await?;
+        
+        if self.options.clear_graph {
+            self.clear_graph().await?;
+        }
+        
+        // Create indexes and constraints first (for better performance)
+        if self.options.skip_schema {
+            info!("\n⏭️ Skipping index and constraint creation (--skip-schema)");
+        } else {
+            info!("\n🗼️ Setting up database schema...");
+            if self.options.skip_id_index {
+                warn!("⚠️ Skipping the automatic id indexes (--skip-id-index): edges are matched to nodes by id, \
+                       which scans every node of the label unless indexes.csv indexes it");
+            } else {
+                self.create_id_indexes_for_all_labels().await?;
+            }
+            self.create_indexes_from_csv().await?;
+            self.create_supporting_indexes_for_constraints().await?;
+            self.create_constraints_from_csv().await?;
+        }
+        
+        // Load nodes first
+        let nodes_start_time = Instant::now();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("\n[{}] 📥 Loading nodes...", timestamp);
+        
+        let mut total_nodes_loaded = 0;
+        for (file_idx, node_file) in loaded_node_files.iter().enumerate() {
+            if self.progress_interval > 0 {
+                info!("📁 Processing node file {}/{}: {:?}", 
+                      file_idx + 1, loaded_node_files.len(), node_file.file_name().unwrap_or_default());
+            }
+            
+            let file_records = if self.progress_interval > 0 {
+                // Counted once per file, shared with the loader's own progress reporting
+                self.file_record_count(node_file)
+            } else {
+                0
+            };
+            
+            // Check for termination before processing each file
+            if self.terminate_on_error.load(Ordering::Relaxed) {
+                return Err(anyhow!("Loading terminated due to critical errors in previous operations"));
+            }
+            
+            match self.load_nodes_batch(node_file, batch_size).await {
+                Ok(_) => {
+                    info!("✓ Successfully loaded node file: {:?}", node_file.file_name().unwrap_or_default());
+                    #[cfg(feature = "pushgateway")]
+                    if self.options.push_each_file {
+                        self.push_metrics(true).await;
+                    }
+                    
+                    // Run once no file of the label is left, wherever --file-order put its part files
+                    for label in &file_labels[file_idx] {
+                        if file_labels[file_idx + 1..].iter().any(|next| next.contains(label)) {
+                            continue;
+                        }
+                        if let Err(e) = self.run_post_load_queries(label).await {
+                            error!("❌ {}", e);
+                            self.terminate_on_error.store(true, Ordering::Relaxed);
+                            return Err(e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("❌ Failed to load node file {:?}: {}", node_file.file_name().unwrap_or_default(), e);
+                    self.terminate_on_error.store(true, Ordering::Relaxed);
+                    return Err(anyhow!("Critical error loading nodes from {:?}: {}", node_file, e));
+                }
+            }
+            
+            total_nodes_loaded += file_records;
+            if self.progress_interval > 0 && total_node_records > 0 {
+                let overall_progress = (total_nodes_loaded as f64 / total_node_records as f64) * 100.0;
+                info!("🎯 Overall node progress: {:.1}% ({}/{})", 
+                      overall_progress, total_nodes_loaded, total_node_records);
+            }
+        }
+        
+        let nodes_duration = nodes_start_time.elapsed();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] ✅ All nodes loaded (Total duration: {:?})", timestamp, nodes_duration);
+        
+        // Edge endpoints are matched by id, so let the id indexes finish populating first
+        if let Some(timeout) = self.options.wait_for_indexes.filter(|_| !self.options.nodes_only) {
+            self.wait_for_indexes(timeout).await?;
+        }
+        if self.options.require_indexes && !self.options.nodes_only && !self.options.dry_run {
+            self.require_edge_indexes(&node_files, &edge_files).await?;
+        }
+        
+        // Then load edges
+        let edges_start_time = Instant::now();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("\n[{}] 🔗 Loading edges...", timestamp);
+        
+        let mut total_edges_loaded = 0;
+        for (file_idx, edge_file) in loaded_edge_files.iter().enumerate() {
+            if self.progress_interval > 0 {
+                info!("📁 Processing edge file {}/{}: {:?}", 
+                      file_idx + 1, loaded_edge_files.len(), edge_file.file_name().unwrap_or_default());
+            }
+            
+            let file_records = if self.progress_interval > 0 {
+                // Counted once per file, shared with the loader's own progress reporting
+                self.file_record_count(edge_file)
+            } else {
+                0
+            };
+            
+            // Check for termination before processing each file
+            if self.terminate_on_error.load(Ordering::Relaxed) {
+                return Err(anyhow!("Loading terminated due to critical errors in previous operations"));
+            }
+            
+            match self.load_edges_batch(edge_file, batch_size).await {
+                Ok(_) => {
+                    info!("✓ Successfully loaded edge file: {:?}", edge_file.file_name().unwrap_or_default());
+                    #[cfg(feature = "pushgateway")]
+                    if self.options.push_each_file {
+                        self.push_metrics(true).await;
+                    }
+                }
+                Err(e) => {
+                    error!("❌ Failed to load edge file {:?}: {}", edge_file.file_name().unwrap_or_default(), e);
+                    self.terminate_on_error.store(true, Ordering::Relaxed);
+                    return Err(anyhow!("Critical error loading edges from {:?}: {}", edge_file, e));
+                }
+            }
+            
+            total_edges_loaded += file_records;
+            if self.progress_interval > 0 && total_edge_records > 0 {
+                let overall_progress = (total_edges_loaded as f64 / total_edge_records as f64) * 100.0;
+                info!("🎯 Overall edge progress: {:.1}% ({}/{})", 
+                      overall_progress, total_edges_loaded, total_edge_records);
+            }
+        }
+        
+        let edges_duration = edges_start_time.elapsed();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("[{}] ✅ All edges loaded (Total duration: {:?})", timestamp, edges_duration);
+        
+        let total_duration = nodes_start_time.elapsed();
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S");
+        info!("\n[{}] ✅ Successfully loaded data into graph '{}' (Total loading time: {:?})",
+              timestamp, self.graph_name, total_duration);
+        // All batches are in; markers are only needed to resume an interrupted load
+        if self.idempotent_batches_enabled() {
+            self.execute_graph_query("MATCH (m:_LoaderBatch) DELETE m").await?;
+        }
+        info!("   Rows loaded so far: {}, failed: {}",
+              self.metrics.rows_loaded.load(Ordering::Relaxed),
+              self.metrics.rows_failed.load(Ordering::Relaxed));
+        if self.options.detect_conflicts {
+            info!("   Conflicting properties overwritten: {}", self.metrics.conflicts.load(Ordering::Relaxed));
+        }
+        if self.options.verify_endpoints {
+            info!("   Edges skipped due to missing endpoints: {}", self.metrics.missing_endpoints.load(Ordering::Relaxed));
+        }
+        if let Some(target) = self.options.throughput_target {
+            let throttled = self.throughput_schedule.lock().unwrap_or_else(|e| e.into_inner()).1;
+            info!("   Batches held back {:?} to stay under {} rows/s", throttled, target);
+        }
+        self.report_slowest_batches();
+        self.report_column_stats();
+        if let Some(bar) = self.overall_bar.take() {
+            bar.finish_and_clear();
+        }
+        
+        // Nothing was written in a dry run, so there is nothing to verify
+        if self.options.dry_run {
+            info!("🧪 Dry run complete, no queries were sent");
+            return Ok(());
+        }
+        if let Some(sample_size) = self.options.verify_sample {
+            self.verify_sampled_rows(loaded_node_files, loaded_edge_files, sample_size).await?;
+        }
+        if self.options.checksum {
+            self.verify_checksums().await?;
+        }
+        if self.options.verify {
+            self.verify_counts(loaded_node_files, loaded_edge_files).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Load CSV files from tenant subdirectories into separate graphs
+    async fn load_multi_graph_csvs(&mut self, batch_size: usize) -> Result<()> {
+        // Find all tenant subdirectories
+        let mut tenant_dirs = Vec::new();
+        
+        if Self::is_csv_archive(&self.csv_dir) {
+            return Err(anyhow!("Multi-graph mode does not support loading from an archive; extract {:?} first", self.csv_dir));
+        }
+        
+        for entry in std::fs::read_dir(&self.csv_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                if let Some(dir_name) = path.file_name() {
+                    let dir_name_str = dir_name.to_string_lossy();
+                    if dir_name_str.starts_with("tenant_") {
+                        tenant_dirs.push(path);
+                    }
+                }
+            }
+        }
+        
+        if tenant_dirs.is_empty() {
+            warn!("⚠️  No tenant subdirectories found in {:?}", self.csv_dir);
+            warn!("   Falling back to single-graph mode...");
+            return self.load_single_graph_csvs(batch_size).await;
+        }
+        
+        tenant_dirs.sort();
+        let tenant_count = tenant_dirs.len();
+        info!("\n🗂️  Found {} tenant directories", tenant_count);
+        info!("   Each will be loaded into a separate graph\n");
+        
+        let overall_start_time = Instant::now();
+        
+        for tenant_path in &tenant_dirs {
+            let tenant_name = tenant_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .replace("tenant_", "");
+            
+            let graph_name = format!("{}_{}", self.base_graph_name, tenant_name);
+            
+            info!("\n{}", "=".repeat(80));
+            info!("📊 Processing tenant: {}", tenant_name);
+            info!("   Target graph: {}", graph_name);
+            info!("   Source directory: {:?}", tenant_path);
+            info!("{}
+", "=".repeat(80));
+            
+            // Switch to this tenant's graph
+            self.graph_name = graph_name.clone();
+            
+            // Temporarily update csv_dir to point to tenant directory
+            let original_csv_dir = self.csv_dir.clone();
+            self.csv_dir = tenant_path.clone();
+            
+            // Load this tenant's data
+            let tenant_start_time = Instant::now();
+            match self.load_single_graph_csvs(batch_size).await {
+                Ok(_) => {
+                    let tenant_duration = tenant_start_time.elapsed();
+                    info!("\n✅ Completed loading tenant '{}' in {:?}", tenant_name, tenant_duration);
+                }
+                Err(e) => {
+                    error!("\n❌ Error loading tenant '{}': {}", tenant_name, e);
+                }
+            }
+            
+            // Restore original csv_dir
+            self.csv_dir = original_csv_dir;
+        }
+        
+        let overall_duration = overall_start_time.elapsed();
+        info!("\n{}", "=".repeat(80));
+        info!("✅ Multi-graph loading complete!");
+        info!("   Loaded {} tenants into separate graphs", tenant_count);
+        info!("   Total time: {:?}", overall_duration);
+        info!("{}", "=".repeat(80));
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,535 @@
+//! Sampling, load plans and config validation, none of which need a connection.
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::loader::FalkorDBCSVLoader;
+use crate::options::{ColumnType, Delimiter, IdentifierStyle, LoaderOptions, PlanFormat};
+use crate::rejects::REJECT_REASON_COLUMN;
+
+impl FalkorDBCSVLoader {
+    /// Print each node/edge file's derived label or type, its columns with the value types
+    /// inferred from the sample, and the first `sample_rows` rows as they would be loaded
+    pub fn sample_csv_files(csv_dir: &Path, sample_rows: usize, multi_graph: bool, options: &LoaderOptions) -> Result<()> {
+        if !csv_dir.exists() {
+            return Err(anyhow!("Directory {:?} does not exist", csv_dir));
+        }
+        
+        let mut dirs = vec![csv_dir.to_path_buf()];
+        if multi_graph && Self::is_csv_archive(csv_dir) {
+            return Err(anyhow!("Multi-graph mode does not support loading from an archive; extract {:?} first", csv_dir));
+        }
+        if multi_graph {
+            for entry in std::fs::read_dir(csv_dir)? {
+                let path = entry?.path();
+                if path.is_dir() && path.file_name().is_some_and(|n| n.to_string_lossy().starts_with("tenant_")) {
+                    dirs.push(path);
+                }
+            }
+            dirs.sort();
+        }
+        
+        let empty_positions = HashMap::new();
+        let edge_key_columns = ["source", "target", "type", "source_label", "target_label"];
+        
+        for dir in &dirs {
+            let mut files = Self::list_csv_dir(dir)?;
+            files.sort();
+            
+            for file_path in files {
+                let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let (is_node, name) = match (Self::entity_name_from_file_name(&file_name, "nodes_"),
+                                             Self::entity_name_from_file_name(&file_name, "edges_")) {
+                    (Some(label), _) => {
+                        let label = match options.identifier_style {
+                            IdentifierStyle::Sanitize => Self::sanitize_label(label),
+                            IdentifierStyle::Backtick => label.to_string(),
+                        };
+                        (true, options.label_case.apply(&label))
+                    }
+                    (None, Some(rel_type)) => (false, options.label_case.apply(rel_type)),
+                    (None, None) => continue,
+                };
+                let column_types = Self::entity_name_from_file_name(&file_name, if is_node { "nodes_" } else { "edges_" })
+                    .and_then(|raw_name| options.schema.get(raw_name));
+                let string_keys: HashSet<String> = column_types.into_iter().flatten()
+                    .filter(|(_, column_type)| **column_type == ColumnType::String)
+                    .map(|(column, _)| column.clone())
+                    .collect();
+                
+                let mut rdr = Self::csv_reader(&file_path, options.delimiter.0)?;
+                let headers = rdr.headers()?.clone();
+                let mut rows = Vec::new();
+                for result in rdr.deserialize::<HashMap<String, String>>().take(sample_rows) {
+                    let mut row = result?;
+                    row.remove(REJECT_REASON_COLUMN);
+                    options.trim_values.apply(&mut row);
+                    rows.push(row);
+                }
+                
+                // Types per column as value_to_cypher_literal would infer them, or from --schema
+                let columns: Vec<String> = headers.iter()
+                    .filter(|column| *column != REJECT_REASON_COLUMN)
+                    .map(|column| {
+                        let types: BTreeSet<&str> = rows.iter()
+                            .map(|row| match (row.get(column).map_or("", |v| v.as_str()), column_types.and_then(|types| types.get(column))) {
+                                ("", _) => "null",
+                                (_, Some(column_type)) => column_type.name(),
+                                (value, None) if Self::array_elements(column, value, options, None).is_some() => "list",
+                                (value, None) => Self::inferred_type(value, options),
+                            })
+                            .collect();
+                        let types: Vec<&str> = types.into_iter().collect();
+                        format!("{} ({})", column, if types.is_empty() { "no data".to_string() } else { types.join("|") })
+                    })
+                    .collect();
+                
+                println!("\n📄 {:?}", file_path);
+                if is_node {
+                    println!("   Label: :{}", name);
+                } else {
+                    println!("   Relationship type: :{}", name);
+                }
+                println!("   Columns: {}", columns.join(", "));
+                
+                for (i, row) in rows.iter().enumerate() {
+                    let mut properties = HashMap::new();
+                    for (key, value) in row {
+                        let skipped = if is_node {
+                            Self::is_node_key_column(key, options)
+                        } else {
+                            edge_key_columns.contains(&key.as_str()) || options.rel_type_column.as_ref() == Some(key)
+                        };
+                        if options.topology_only || skipped || value.is_empty() {
+                            continue;
+                        }
+                        // Same key cleanup as edge loading: 'Date:Date' -> 'Date'
+                        let key = match key.split_once(':') {
+                            Some((first, second)) if first == second => first.to_string(),
+                            _ => key.clone(),
+                        };
+                        properties.insert(key, value.clone());
+                    }
+                    let props = Self::build_cypher_map(&properties, &string_keys, &empty_positions, options, column_types);
+                    let value = |column: &str| Self::value_to_cypher_literal(row.get(column).map_or("", |v| v.as_str()));
+                    
+                    if is_node {
+                        println!("   Row {}: id: {}, props: {}", i + 1, value("id"), props);
+                    } else {
+                        let label = |column: &str| row.get(column)
+                            .filter(|l| !l.trim().is_empty())
+                            .map(|l| format!(":{}", options.label_case.apply(l.trim())))
+                            .unwrap_or_default();
+                        println!("   Row {}: ({} {{id: {}}})-[:{} {}]->({} {{id: {}}})",
+                                 i + 1, label("source_label"), value("source"), name, props,
+                                 label("target_label"), value("target"));
+                    }
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Graph name of a label as the load would create it (`label_name` followed by
+    /// `graph_label`), for use before a loader exists
+    fn planned_label(options: &LoaderOptions, raw_label: &str) -> String {
+        let label = match options.identifier_style {
+            IdentifierStyle::Sanitize => Self::sanitize_label(raw_label),
+            IdentifierStyle::Backtick => raw_label.to_string(),
+        };
+        let label = options.label_case.apply(&label);
+        match &options.label_prefix {
+            Some(prefix) if options.identifier_style == IdentifierStyle::Sanitize => label.split(':')
+                .map(|part| format!("{}{}", prefix, part))
+                .collect::<Vec<_>>()
+                .join(":"),
+            Some(prefix) => format!("{}{}", prefix, label),
+            None => label,
+        }
+    }
+    
+    /// Print the ordered phases of a load (lock, schema, node files, edge files, cleanup and
+    /// verification) per target graph without connecting or reading data rows. Edge endpoint
+    /// labels come from each file's first row, like `validate_label_consistency`.
+    pub fn print_load_plan(csv_dir: &Path, graph_name: &str, multi_graph: bool, merge_mode: bool,
+                           options: &LoaderOptions, format: PlanFormat) -> Result<()> {
+        if !csv_dir.exists() {
+            return Err(anyhow!("Directory {:?} does not exist", csv_dir));
+        }
+        
+        let mut targets = Vec::new();
+        if multi_graph && Self::is_csv_archive(csv_dir) {
+            return Err(anyhow!("Multi-graph mode does not support loading from an archive; extract {:?} first", csv_dir));
+        }
+        if multi_graph {
+            for entry in std::fs::read_dir(csv_dir)? {
+                let path = entry?.path();
+                let tenant = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if path.is_dir() && tenant.starts_with("tenant_") {
+                    targets.push((format!("{}_{}", graph_name, tenant.replace("tenant_", "")), path));
+                }
+            }
+            targets.sort();
+        }
+        if targets.is_empty() {
+            targets.push((graph_name.to_string(), csv_dir.to_path_buf()));
+        }
+        
+        let mode = if options.properties_only {
+            "properties-only"
+        } else if merge_mode {
+            "merge"
+        } else {
+            "create"
+        };
+        let step = |action: String| serde_json::json!({ "action": action });
+        let mut graphs = Vec::new();
+        let mut warnings = Vec::new();
+        
+        for (graph, dir) in &targets {
+            let mut files = Self::list_csv_dir(dir)?;
+            files.retain(|path| Self::is_selected_file(options, &path.file_name().unwrap_or_default().to_string_lossy()));
+            Self::order_files(&mut files, options.file_order)?;
+            let file_name = |path: &PathBuf| path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            
+            let node_files: Vec<String> = files.iter()
+                .map(file_name)
+                .filter(|name| Self::entity_name_from_file_name(name, "nodes_").is_some())
+                .collect();
+            let mut node_labels = BTreeSet::new();
+            let mut node_steps = Vec::new();
+            for (i, name) in node_files.iter().enumerate() {
+                let raw_label = Self::entity_name_from_file_name(name, "nodes_").unwrap_or_default();
+                let label = Self::planned_label(options, raw_label);
+                node_steps.push(serde_json::json!({
+                    "action": format!("Load {} as :{}", name, label),
+                    "file": name,
+                    "label": label,
+                }));
+                node_labels.insert(label.clone());
+                
+                // Post-load queries follow the last file of their label, keyed like `label_name`
+                let last_of_label = !node_files[i + 1..].iter()
+                    .any(|next| Self::entity_name_from_file_name(next, "nodes_") == Some(raw_label));
+                let key = match options.identifier_style {
+                    IdentifierStyle::Sanitize => Self::sanitize_label(raw_label),
+                    IdentifierStyle::Backtick => raw_label.to_string(),
+                };
+                if let Some(queries) = options.post_load_queries.get(&key).filter(|_| last_of_label) {
+                    node_steps.push(serde_json::json!({
+                        "action": format!("Run {} post-load query(s) for :{}", queries.len(), label),
+                        "label": label,
+                        "queries": queries,
+                    }));
+                }
+            }
+            
+            let mut edge_steps = Vec::new();
+            for path in &files {
+                let name = file_name(path);
+                let Some(raw_type) = Self::entity_name_from_file_name(&name, "edges_") else {
+                    continue;
+                };
+                let rel_type = match options.rel_type_template.clone()
+                    .or_else(|| options.rel_type_column.as_ref().map(|column| format!("{{{}}}", column))) {
+                    Some(template) => template,
+                    None => {
+                        let rel_type = options.label_case.apply(raw_type);
+                        format!("{}{}", options.rel_type_prefix.as_deref().unwrap_or(""), rel_type)
+                    }
+                };
+                let first_row = Self::csv_reader(path, options.delimiter.0)?
+                    .deserialize::<HashMap<String, String>>()
+                    .next()
+                    .transpose()?
+                    .unwrap_or_default();
+                let mut endpoint = |column: &str| -> Option<String> {
+                    let raw_label = first_row.get(column).map(|l| l.trim()).filter(|l| !l.is_empty())?;
+                    let label = Self::planned_label(options, raw_label);
+                    let known = node_labels.contains(&label)
+                        || node_labels.iter().any(|l| l.eq_ignore_ascii_case(&label));
+                    if !known {
+                        warnings.push(format!("{}: {} '{}' has no node file in {:?}", name, column, raw_label, dir));
+                    }
+                    Some(label)
+                };
+                let (source_label, target_label) = (endpoint("source_label"), endpoint("target_label"));
+                let pattern = |label: &Option<String>| label.as_ref().map_or("()".to_string(), |l| format!("(:{})", l));
+                edge_steps.push(serde_json::json!({
+                    "action": format!("Load {} as {}-[:{}]->{}", name, pattern(&source_label), rel_type, pattern(&target_label)),
+                    "file": name,
+                    "type": rel_type,
+                    "source_label": source_label,
+                    "target_label": target_label,
+                }));
+            }
+            
+            let mut schema_steps = Vec::new();
+            if options.skip_id_index {
+                schema_steps.push(step("Skip automatic id indexes (--skip-id-index)".to_string()));
+            } else {
+                schema_steps.extend(node_labels.iter().map(|label| step(format!("CREATE INDEX ON :{}(id)", label))));
+            }
+            for (definition_file, what) in [("indexes.csv", "index"), ("constraints.csv", "constraint")] {
+                let path = dir.join(definition_file);
+                if Self::csv_file_exists(&path) {
+                    let count = Self::csv_reader(&path, options.delimiter.0)?.records().count();
+                    schema_steps.push(step(format!("Create {} {} definition(s) from {}", count, what, definition_file)));
+                }
+            }
+            
+            let mut phases = Vec::new();
+            let mut phase = |name: &str, steps: Vec<serde_json::Value>| {
+                if !steps.is_empty() {
+                    phases.push(serde_json::json!({ "phase": name, "steps": steps }));
+                }
+            };
+            if options.lock {
+                phase("lock", vec![step(format!("Acquire the loader lock on graph '{}'", graph))]);
+            }
+            if !options.skip_schema {
+                phase("schema", schema_steps);
+            }
+            if !options.edges_only {
+                phase("nodes", node_steps);
+            }
+            if !options.nodes_only {
+                if let Some(timeout) = options.wait_for_indexes {
+                    phase("wait_for_indexes", vec![step(format!("Wait up to {:?} for indexes to finish populating", timeout))]);
+                }
+                if options.require_indexes && !edge_steps.is_empty() {
+                    phase("require_indexes", vec![step("Abort unless the id and endpoint key indexes exist".to_string())]);
+                }
+                phase("edges", edge_steps);
+            }
+            let mut cleanup_steps = Vec::new();
+            if options.idempotent_batches && !merge_mode && !options.properties_only {
+                cleanup_steps.push(step("Delete (:_LoaderBatch) markers".to_string()));
+            }
+            if options.lock {
+                cleanup_steps.push(step(format!("Release the loader lock on graph '{}'", graph)));
+            }
+            phase("cleanup", cleanup_steps);
+            let mut verification_steps = Vec::new();
+            if let Some(sample_size) = options.verify_sample {
+                verification_steps.push(step(format!("Verify {} sampled rows per file", sample_size)));
+            }
+            if options.checksum {
+                verification_steps.push(step("Compare checksums of the sent data with the graph".to_string()));
+            }
+            if options.verify {
+                verification_steps.push(step("Compare CSV row counts per label and relationship type with the graph".to_string()));
+            }
+            phase("verification", verification_steps);
+            
+            graphs.push(serde_json::json!({
+                "graph": graph,
+                "csv_dir": dir.display().to_string(),
+                "mode": mode,
+                "phases": phases,
+            }));
+        }
+        
+        match format {
+            PlanFormat::Json => {
+                let plan = serde_json::json!({ "graphs": graphs, "warnings": warnings });
+                println!("{}", serde_json::to_string_pretty(&plan)?);
+            }
+            PlanFormat::Text => {
+                for graph in &graphs {
+                    println!("\n📋 Load plan for graph '{}' from {} ({} mode)",
+                             graph["graph"].as_str().unwrap_or(""), graph["csv_dir"], graph["mode"].as_str().unwrap_or(""));
+                    for (i, phase) in graph["phases"].as_array().into_iter().flatten().enumerate() {
+                        println!("   {}. {}", i + 1, phase["phase"].as_str().unwrap_or(""));
+                        for step in phase["steps"].as_array().into_iter().flatten() {
+                            println!("      - {}", step["action"].as_str().unwrap_or(""));
+                        }
+                    }
+                }
+                for warning in &warnings {
+                    println!("⚠️ {}", warning);
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Check indexes.csv, constraints.csv and the node/edge file headers without connecting.
+    /// Every problem is printed with its file, line and field; returns whether no errors were found.
+    pub fn validate_config(csv_dir: &Path, multi_graph: bool, delimiter: Delimiter) -> Result<bool> {
+        if !csv_dir.exists() {
+            return Err(anyhow!("Directory {:?} does not exist", csv_dir));
+        }
+        if multi_graph && Self::is_csv_archive(csv_dir) {
+            return Err(anyhow!("Multi-graph mode does not support loading from an archive; extract {:?} first", csv_dir));
+        }
+        
+        let mut dirs = vec![csv_dir.to_path_buf()];
+        if multi_graph {
+            dirs.clear();
+            for entry in std::fs::read_dir(csv_dir)? {
+                let path = entry?.path();
+                if path.is_dir() && path.file_name().is_some_and(|n| n.to_string_lossy().starts_with("tenant_")) {
+                    dirs.push(path);
+                }
+            }
+            dirs.sort();
+            if dirs.is_empty() {
+                println!("❌ {:?}: no tenant_* directories found for --multi-graph", csv_dir);
+                return Ok(false);
+            }
+        }
+        
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let field = |row: &csv::StringRecord, headers: &csv::StringRecord, name: &str| -> String {
+            headers.iter().position(|h| h == name)
+                .and_then(|i| row.get(i))
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        };
+        
+        for dir in &dirs {
+            let indexes_file = dir.join("indexes.csv");
+            if Self::csv_file_exists(&indexes_file) {
+                let mut rdr = Self::csv_reader(&indexes_file, delimiter.0)?;
+                let headers = rdr.headers()?.clone();
+                for column in ["labels", "properties"] {
+                    if !headers.iter().any(|h| h == column) {
+                        errors.push(format!("{:?}: missing required column '{}'", indexes_file, column));
+                    }
+                }
+                for result in rdr.records() {
+                    let row = match result {
+                        Ok(row) => row,
+                        Err(e) => {
+                            errors.push(format!("{:?}: {}", indexes_file, e));
+                            break;
+                        }
+                    };
+                    let line = row.position().map_or(0, |p| p.line());
+                    let at = |field_name: &str| format!("{:?} line {}, field '{}'", indexes_file, line, field_name);
+                    let index_type = field(&row, &headers, "type").to_uppercase();
+                    if index_type == "LOOKUP" || field(&row, &headers, "uniqueness") == "UNIQUE" {
+                        continue;
+                    }
+                    for required in ["labels", "properties"] {
+                        if field(&row, &headers, required).is_empty() {
+                            warnings.push(format!("{}: empty, the index will be skipped", at(required)));
+                        }
+                    }
+                    match index_type.as_str() {
+                        "" | "RANGE" | "FULLTEXT" => {}
+                        "VECTOR" => {
+                            let dimension = field(&row, &headers, "dimension");
+                            if !dimension.parse::<usize>().is_ok_and(|d| d > 0) {
+                                errors.push(format!("{}: expected a positive integer, got '{}'", at("dimension"), dimension));
+                            }
+                            let similarity = field(&row, &headers, "similarity").to_lowercase();
+                            if !similarity.is_empty() && similarity != "euclidean" && similarity != "cosine" {
+                                errors.push(format!("{}: expected 'euclidean' or 'cosine', got '{}'", at("similarity"), similarity));
+                            }
+                        }
+                        other => warnings.push(format!("{}: unknown index type '{}', a range index will be created", at("type"), other)),
+                    }
+                }
+            }
+            
+            let constraints_file = dir.join("constraints.csv");
+            if Self::csv_file_exists(&constraints_file) {
+                let mut rdr = Self::csv_reader(&constraints_file, delimiter.0)?;
+                let headers = rdr.headers()?.clone();
+                for column in ["labels", "properties", "type"] {
+                    if !headers.iter().any(|h| h == column) {
+                        errors.push(format!("{:?}: missing required column '{}'", constraints_file, column));
+                    }
+                }
+                for result in rdr.records() {
+                    let row = match result {
+                        Ok(row) => row,
+                        Err(e) => {
+                            errors.push(format!("{:?}: {}", constraints_file, e));
+                            break;
+                        }
+                    };
+                    let line = row.position().map_or(0, |p| p.line());
+                    let at = |field_name: &str| format!("{:?} line {}, field '{}'", constraints_file, line, field_name);
+                    for required in ["labels", "properties"] {
+                        if field(&row, &headers, required).is_empty() {
+                            errors.push(format!("{}: must not be empty", at(required)));
+                        }
+                    }
+                    let constraint_type = field(&row, &headers, "type").to_uppercase();
+                    if !constraint_type.contains("UNIQUE") {
+                        warnings.push(format!("{}: constraint type '{}' is not supported and will be skipped", at("type"), constraint_type));
+                    }
+                    let entity_type = field(&row, &headers, "entity_type").to_uppercase();
+                    if !entity_type.is_empty() && entity_type != "NODE" {
+                        warnings.push(format!("{}: only NODE constraints are supported, got '{}'", at("entity_type"), entity_type));
+                    }
+                }
+            }
+            
+            let mut files = Self::list_csv_dir(dir)?;
+            files.sort();
+            for file_path in files {
+                let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let required: &[&str] = if Self::entity_name_from_file_name(&file_name, "nodes_").is_some() {
+                    &["id"]
+                } else if Self::entity_name_from_file_name(&file_name, "edges_").is_some() {
+                    &["source", "target"]
+                } else {
+                    continue;
+                };
+                let headers = match Self::csv_reader(&file_path, delimiter.0).and_then(|mut rdr| Ok(rdr.headers()?.clone())) {
+                    Ok(headers) => headers,
+                    Err(e) => {
+                        errors.push(format!("{:?}: cannot read header: {}", file_path, e));
+                        continue;
+                    }
+                };
+                for column in required {
+                    if !headers.iter().any(|h| h == *column) {
+                        errors.push(format!("{:?}: missing required column '{}'", file_path, column));
+                    }
+                }
+            }
+        }
+        
+        for warning in &warnings {
+            println!("⚠️ {}", warning);
+        }
+        for error in &errors {
+            println!("❌ {}", error);
+        }
+        if errors.is_empty() {
+            println!("✅ Configuration is valid ({} warning(s))", warnings.len());
+        } else {
+            println!("❌ {} error(s), {} warning(s)", errors.len(), warnings.len());
+        }
+        Ok(errors.is_empty())
+    }
+    
+    /// Name of the Cypher type a CSV value is loaded as
+    fn inferred_type(value: &str, options: &LoaderOptions) -> &'static str {
+        if value.is_empty() {
+            "null"
+        } else if Self::inferred_bool(value, false, options).is_some() {
+            "boolean"
+        } else if let Some(temporal) = Self::inferred_temporal(value, false, options) {
+            if temporal.starts_with("date(") { "date" } else { "localdatetime" }
+        } else if Self::is_formatted_number(value) {
+            "string"
+        } else if value.parse::<i64>().is_ok() {
+            "integer"
+        } else if value.parse::<f64>().is_ok() {
+            "float"
+        } else {
+            "string"
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Rejected rows (`--rejects-dir`) and dead letters (`--dead-letter-dir`).
+
+use anyhow::{anyhow, Result};
+use csv::StringRecord;
+use log::{error, info};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::files::RawCsvRecord;
+use crate::loader::FalkorDBCSVLoader;
+
+/// Column holding the reason in `--rejects-dir` and `--dead-letter-dir` files; ignored when files are loaded
+pub(crate) const REJECT_REASON_COLUMN: &str = "_reject_reason";
+
+/// Reject reason for `--properties-only` rows whose node or relationship doesn't exist
+pub(crate) const TARGET_NOT_FOUND: &str = "target not found";
+
+/// `--dead-letter-dir` files of the run, started on the first failed row of their label or type.
+/// A file is only held open while a source file writes to it, so a run with thousands of
+/// labels or types never holds more than one of them open.
+#[derive(Default)]
+pub(crate) struct DeadLetters {
+    /// Header of each dead-letter file started in this run
+    headers: HashMap<PathBuf, StringRecord>,
+    /// Writers of the source file being loaded, closed by `close_dead_letters`
+    writers: HashMap<PathBuf, csv::Writer<File>>,
+    /// Header of each source file that had failed rows
+    source_headers: HashMap<String, StringRecord>,
+}
+
+impl FalkorDBCSVLoader {
+    /// Reset the consecutive failure count after a batch or row query succeeded
+    pub(crate) fn record_query_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+    }
+    
+    /// Count a failed batch or row query, stopping the load once `--max-consecutive-failures`
+    /// queries in a row have failed
+    pub(crate) fn record_query_failure(&self, file_name: &str) -> Result<()> {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let limit = self.options.max_consecutive_failures;
+        if limit > 0 && failures >= limit {
+            error!("🛑 {} queries in a row failed, the last in {}; stopping the load (--max-consecutive-failures {})",
+                   failures, file_name, limit);
+            self.terminate_on_error.store(true, Ordering::Relaxed);
+            return Err(anyhow!("{} consecutive query failures in {}", failures, file_name));
+        }
+        Ok(())
+    }
+    
+    /// Log the source location of a row that failed to load and append it to the rejects file
+    pub(crate) fn report_rejected_row(&self, file_name: &str, raw: &RawCsvRecord, reason: &str) -> Result<()> {
+        self.metrics.rows_failed.fetch_add(1, Ordering::Relaxed);
+        let raw_line = raw.to_csv_line();
+        error!("   Source row: {} line {}: {}", file_name, raw.line, raw_line);
+        
+        if let Some(output_path) = &self.options.rejects_output {
+            let file = std::fs::OpenOptions::new().append(true).create(true).open(output_path)?;
+            let mut writer = csv::Writer::from_writer(file);
+            writer.write_record([file_name, raw.line.to_string().as_str(), reason, raw_line.as_str()])?;
+            writer.flush()?;
+        }
+        if let Some(dir) = &self.options.rejects_dir {
+            self.write_source_format_reject(dir, file_name, raw, reason)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Append a rejected row to `DIR/<file>` (`DIR/tenant_<name>/<file>` in multi-graph mode) in
+    /// the source file's format, with the reason in the `_reject_reason` column. The first reject
+    /// of a file in this run starts it with the source header. Fields quoted in the source stay
+    /// quoted when tracked (`--quoted-as-string`).
+    fn write_source_format_reject(&self, dir: &Path, file_name: &str, raw: &RawCsvRecord, reason: &str) -> Result<()> {
+        let mut path = dir.to_path_buf();
+        if let Some(tenant) = self.csv_dir.file_name().filter(|name| self.multi_graph_mode && name.to_string_lossy().starts_with("tenant_")) {
+            path.push(tenant);
+        }
+        // Rejects of a compressed file are written uncompressed
+        path.push(file_name.strip_suffix(".gz").unwrap_or(file_name));
+        
+        let delimiter = self.options.delimiter.0;
+        let separator = char::from(delimiter).to_string();
+        let mut started = self.reject_file_headers.lock().unwrap_or_else(|e| e.into_inner());
+        let is_new = !started.contains_key(&path);
+        if is_new {
+            // Rejects of a rejects file replace its reason instead of adding a second column
+            let mut headers = Self::csv_reader(&self.csv_dir.join(file_name), delimiter)?.headers()?.clone();
+            if !headers.iter().any(|h| h == REJECT_REASON_COLUMN) {
+                headers.push_field(REJECT_REASON_COLUMN);
+            }
+            started.insert(path.clone(), headers);
+        }
+        let headers = &started[&path];
+        
+        let line = headers.iter().enumerate()
+            .map(|(i, column)| match column {
+                REJECT_REASON_COLUMN => RawCsvRecord::csv_field(reason, false, delimiter),
+                _ => RawCsvRecord::csv_field(raw.record.get(i).unwrap_or(""), raw.quoted_columns.contains(column), delimiter),
+            })
+            .collect::<Vec<_>>()
+            .join(&separator);
+        let mut contents = String::new();
+        if is_new {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let header_line = headers.iter().map(|h| RawCsvRecord::csv_field(h, false, delimiter)).collect::<Vec<_>>().join(&separator);
+            contents.push_str(&header_line);
+            contents.push('\n');
+        }
+        contents.push_str(&line);
+        contents.push('\n');
+        
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new().write(true).create(true).append(!is_new).truncate(is_new).open(&path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+    
+    /// Write a row whose query failed to `DIR/failed_nodes_<label>.csv` or `DIR/failed_edges_<type>.csv`
+    /// (`DIR/failed_nodes.csv` or `DIR/failed_edges.csv` for an unnamed `nodes.csv` or `edges.csv`)
+    /// (under `tenant_<name>/` in multi-graph mode) with the source columns and the error. Part
+    /// files of a label or type share one file, whose header comes from the first of them.
+    pub(crate) fn write_dead_letter(&self, file_name: &str, raw: &RawCsvRecord, error: &str) -> Result<()> {
+        let Some(dir) = &self.options.dead_letter_dir else {
+            return Ok(());
+        };
+        let dead_letter_file = match ["nodes", "edges"].into_iter()
+            .find_map(|kind| Self::entity_name_from_file_name(file_name, &format!("{}_", kind)).map(|name| (kind, name))) {
+            Some((kind, name)) => format!("failed_{}_{}.csv", kind, name),
+            None if self.is_node_file(file_name) => "failed_nodes.csv".to_string(),
+            None if self.is_edge_file(file_name) => "failed_edges.csv".to_string(),
+            None => return Ok(()),
+        };
+        let mut path = dir.to_path_buf();
+        if let Some(tenant) = self.csv_dir.file_name().filter(|name| self.multi_graph_mode && name.to_string_lossy().starts_with("tenant_")) {
+            path.push(tenant);
+        }
+        path.push(dead_letter_file);
+        
+        let mut dead_letters = self.dead_letters.lock().unwrap_or_else(|e| e.into_inner());
+        let DeadLetters { headers, writers, source_headers } = &mut *dead_letters;
+        if !source_headers.contains_key(file_name) {
+            let headers = Self::csv_reader(&self.csv_dir.join(file_name), self.options.delimiter.0)?.headers()?.clone();
+            source_headers.insert(file_name.to_string(), headers);
+        }
+        let source_header = &source_headers[file_name];
+        if !headers.contains_key(&path) {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut header: StringRecord = source_header.iter().filter(|h| *h != REJECT_REASON_COLUMN).collect();
+            header.push_field(REJECT_REASON_COLUMN);
+            let mut writer = csv::WriterBuilder::new().delimiter(self.options.delimiter.0).from_path(&path)?;
+            writer.write_record(&header)?;
+            info!("📮 Writing rows that failed to load to {:?}", path);
+            writers.insert(path.clone(), writer);
+            headers.insert(path.clone(), header);
+        }
+        if !writers.contains_key(&path) {
+            // Started by an earlier source file and closed after it; append below its rows
+            let file = std::fs::OpenOptions::new().append(true).open(&path)?;
+            writers.insert(path.clone(), csv::WriterBuilder::new().delimiter(self.options.delimiter.0).from_writer(file));
+        }
+        let header = &headers[&path];
+        let writer = writers.get_mut(&path).expect("dead-letter writer was just opened");
+        
+        // Fields are matched by column name, so part files may order their columns differently
+        let record: StringRecord = header.iter()
+            .map(|column| match column {
+                REJECT_REASON_COLUMN => error,
+                _ => source_header.iter().position(|h| h == column).and_then(|i| raw.record.get(i)).unwrap_or(""),
+            })
+            .collect();
+        writer.write_record(&record)?;
+        Ok(())
+    }
+    
+    /// Flush and close the `--dead-letter-dir` files of a finished source file, so its rows
+    /// are on disk and the next file starts with no dead-letter file open
+    pub(crate) fn close_dead_letters(&self) -> Result<()> {
+        for (_, mut writer) in self.dead_letters.lock().unwrap_or_else(|e| e.into_inner()).writers.drain() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+//! Row filtering, batching and batch markers shared by node and edge loading.
+
+use anyhow::{anyhow, Result};
+use falkordb::FalkorValue;
+use log::warn;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::files::{CsvRows, RawCsvRecord};
+use crate::loader::FalkorDBCSVLoader;
+use crate::options::{BatchHook, BatchKind, ColumnType, OnDuplicateId};
+
+/// `--idempotent-batches` markers an earlier attempt left for one batch
+#[derive(Debug)]
+pub(crate) struct BatchMarkers {
+    pub(crate) key: String,
+    /// The batch query itself committed
+    pub(crate) committed: bool,
+    /// Rows of the batch the per-row fallback committed
+    pub(crate) loaded_rows: HashSet<usize>,
+}
+
+impl FalkorDBCSVLoader {
+    /// Run the batch hook on the JSON rows of a batch and render them back to Cypher items.
+    /// The `vectors` field holds arrays that are wrapped in `vecf32()`, and `weight` stays a float.
+    pub(crate) fn apply_batch_hook(hook: &BatchHook, kind: BatchKind<'_>, mut rows: Vec<serde_json::Value>) -> Vec<String> {
+        (hook.0)(kind, &mut rows);
+        rows.iter()
+            .map(|row| match row {
+                serde_json::Value::Object(fields) => {
+                    let fields: Vec<String> = fields.iter()
+                        .map(|(key, value)| match (key.as_str(), value) {
+                            ("vectors", serde_json::Value::Object(vectors)) => {
+                                let vectors: Vec<String> = vectors.iter()
+                                    .map(|(column, elements)| format!("{}: vecf32({})", column, Self::json_to_cypher_literal(elements)))
+                                    .collect();
+                                format!("vectors: {{{}}}", vectors.join(", "))
+                            }
+                            ("weight", serde_json::Value::Number(weight)) => format!("weight: {}", Self::float_literal(weight.as_f64().unwrap_or_default())),
+                            _ => format!("{}: {}", key, Self::json_to_cypher_literal(value)),
+                        })
+                        .collect();
+                    format!("{{{}}}", fields.join(", "))
+                }
+                other => Self::json_to_cypher_literal(other),
+            })
+            .collect()
+    }
+    
+    /// Estimated size of a row in a batch query: the bytes of its column names and values
+    fn estimated_row_bytes(row: &HashMap<String, String>) -> usize {
+        row.iter().map(|(key, value)| key.len() + value.len()).sum()
+    }
+    
+    /// Apply `--max-row-bytes`: reject rows whose estimated size exceeds the limit so a single
+    /// outlier can't make its whole batch fail and fall back to per-row queries
+    pub(crate) fn drop_oversized_rows(&self, file_name: &str, name: &str, rows: &mut CsvRows, raw_records: &mut Vec<RawCsvRecord>) -> Result<()> {
+        let Some(limit) = self.options.max_row_bytes else {
+            return Ok(());
+        };
+        
+        let mut kept = (Vec::with_capacity(rows.len()), Vec::with_capacity(raw_records.len()));
+        for (row, raw) in rows.drain(..).zip(raw_records.drain(..)) {
+            let size = Self::estimated_row_bytes(&row);
+            if size <= limit {
+                kept.0.push(row);
+                kept.1.push(raw);
+                continue;
+            }
+            let entity = match row.get("id") {
+                Some(id) => format!("id {:?}", id),
+                None => format!("{:?} -> {:?}", row.get("source").map_or("", |v| v.as_str()), row.get("target").map_or("", |v| v.as_str())),
+            };
+            warn!("⚠️ {} row {} in {} line {} is {} bytes, over --max-row-bytes {}, row skipped",
+                  name, entity, file_name, raw.line, size, limit);
+            self.report_rejected_row(file_name, &raw, &format!("row is {} bytes, over the {} byte limit", size, limit))?;
+        }
+        *rows = kept.0;
+        *raw_records = kept.1;
+        Ok(())
+    }
+    
+    /// `--on-duplicate-id`: read the ids of a node file before it is loaded and find those
+    /// already seen for the same labels, in this file or an earlier one of the graph. Fails
+    /// in `error` mode; in `skip` mode returns the lines of the repeated rows.
+    pub(crate) fn check_duplicate_ids(&self, path: &Path, file_name: &str, file_label: Option<&str>) -> Result<HashSet<u64>> {
+        let Some(action) = self.options.on_duplicate_id else {
+            return Ok(HashSet::new());
+        };
+        let mut rdr = Self::csv_reader(path, self.options.delimiter.0)?;
+        let headers = rdr.headers()?.clone();
+        // Rows without an id column get one from their ordinal, which can't repeat
+        let Some(id_index) = headers.iter().position(|h| h == "id") else {
+            return Ok(HashSet::new());
+        };
+        let labels_column = self.labels_column();
+        let labels_index = headers.iter().position(|h| h == labels_column);
+        
+        let mut duplicates = Vec::new();
+        let mut seen = self.seen_node_ids.lock().unwrap_or_else(|e| e.into_inner());
+        for record in rdr.records() {
+            let record = record?;
+            let mut row = HashMap::from([("id".to_string(), record.get(id_index).unwrap_or("").to_string())]);
+            if let Some(index) = labels_index {
+                row.insert(labels_column.to_string(), record.get(index).unwrap_or("").to_string());
+            }
+            self.options.trim_values.apply(&mut row);
+            let Some(label) = self.row_label(&row, file_label).filter(|_| !row["id"].is_empty()) else {
+                continue;
+            };
+            if !seen.entry(label.clone()).or_default().insert(row["id"].clone()) {
+                duplicates.push((record.position().map_or(0, |pos| pos.line()), label, row["id"].clone()));
+            }
+        }
+        drop(seen);
+        
+        if duplicates.is_empty() {
+            return Ok(HashSet::new());
+        }
+        for (line, label, id) in duplicates.iter().take(10) {
+            warn!("⚠️ Duplicate id {:?} for :{} in {} line {}", id, label, file_name, line);
+        }
+        warn!("⚠️ {} duplicate node ids in {}", duplicates.len(), file_name);
+        match action {
+            OnDuplicateId::Warn => Ok(HashSet::new()),
+            OnDuplicateId::Error => Err(anyhow!("{} duplicate node ids in {} (--on-duplicate-id error), the first is {:?} on line {}",
+                                                duplicates.len(), file_name, duplicates[0].2, duplicates[0].0)),
+            OnDuplicateId::Skip => Ok(duplicates.into_iter().map(|(line, _, _)| line).collect()),
+        }
+    }
+    
+    /// Remove the rows on the `--on-duplicate-id skip` lines of a file from a batch and report them
+    pub(crate) fn drop_duplicate_id_rows(&self, file_name: &str, lines: &HashSet<u64>, rows: &mut CsvRows, raw_records: &mut Vec<RawCsvRecord>) -> Result<()> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let mut kept = (Vec::with_capacity(rows.len()), Vec::with_capacity(raw_records.len()));
+        for (row, raw) in rows.drain(..).zip(raw_records.drain(..)) {
+            if lines.contains(&raw.line) {
+                self.report_rejected_row(file_name, &raw, &format!("duplicate id {:?}", row.get("id").map_or("", |id| id.as_str())))?;
+            } else {
+                kept.0.push(row);
+                kept.1.push(raw);
+            }
+        }
+        *rows = kept.0;
+        *raw_records = kept.1;
+        Ok(())
+    }
+    
+    /// Row ranges of each batch, split so that no batch crosses `split`
+    pub(crate) fn batch_ranges(len: usize, batch_size: usize, splits: &[usize]) -> Vec<std::ops::Range<usize>> {
+        let mut bounds: Vec<usize> = splits.iter().map(|&split| split.min(len)).collect();
+        bounds.extend([0, len]);
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds.windows(2)
+            .flat_map(|segment| (segment[0]..segment[1]).step_by(batch_size.max(1)).map(move |i| i..(i + batch_size.max(1)).min(segment[1])))
+            .collect()
+    }
+    
+    /// Whether a property value equals its `--omit-default` default, compared after type
+    /// inference so `1.0` matches a default of `1.0` but not the string `"1.0"`
+    pub(crate) fn is_omitted_default(&self, property: &str, value: &str, quoted: bool) -> bool {
+        self.options.omit_defaults.get(property)
+            .is_some_and(|default| Self::canonical_csv_value(value, quoted) == Self::canonical_csv_value(default, false))
+    }
+    
+    /// `--stamp-source` property name and the string literal stored in it for a file
+    pub(crate) fn source_stamp(&self, file_name: &str) -> Option<(String, String)> {
+        let property = self.options.stamp_source.as_ref()?;
+        let value = self.options.source_tag.as_deref().unwrap_or(file_name);
+        Some((property.clone(), Self::string_literal(value)))
+    }
+    
+    /// Batch markers are only needed where re-running a batch would duplicate data
+    pub(crate) fn idempotent_batches_enabled(&self) -> bool {
+        self.options.idempotent_batches && !self.merge_mode && !self.options.properties_only
+    }
+    
+    /// Deterministic key identifying a batch of a file across attempts and reruns
+    pub(crate) fn batch_key(file_name: &str, batch_num: usize, batch_size: usize) -> String {
+        format!("{}#{}#{}", file_name, batch_size, batch_num)
+    }
+    
+    /// Suffix for a batch query that records its marker in the same (atomic) query
+    pub(crate) fn batch_marker_clause(key: &str) -> String {
+        format!(" WITH count(*) AS batch_rows CREATE (:_LoaderBatch {{key: {}}})", Self::string_literal(key))
+    }
+    
+    /// Query counting the markers of a batch
+    pub(crate) fn batch_marker_lookup(key: &str) -> String {
+        format!("MATCH (m:_LoaderBatch {{key: {}}}) RETURN count(m)", Self::string_literal(key))
+    }
+    
+    /// Key of the marker a fallback row query records for row `row` of a batch
+    pub(crate) fn row_marker_key(batch_key: &str, row: usize) -> String {
+        format!("{}#{}", batch_key, row)
+    }
+    
+    /// Query returning the keys of a batch's marker and of its rows' fallback markers
+    pub(crate) fn batch_markers_lookup(key: &str) -> String {
+        format!("MATCH (m:_LoaderBatch) WHERE m.key = {} OR m.key STARTS WITH {} RETURN m.key",
+                Self::string_literal(key), Self::string_literal(&format!("{}#", key)))
+    }
+    
+    /// Parse the result of `batch_markers_lookup` into what an earlier attempt committed
+    pub(crate) fn parse_batch_markers(key: String, rows: &[Vec<FalkorValue>]) -> BatchMarkers {
+        let row_prefix = format!("{}#", key);
+        let mut markers = BatchMarkers { committed: false, loaded_rows: HashSet::new(), key };
+        for found in rows.iter().filter_map(|row| row.first()).map(Self::falkor_value_to_string) {
+            if found == markers.key {
+                markers.committed = true;
+            } else if let Some(row) = found.strip_prefix(&row_prefix).and_then(|row| row.parse().ok()) {
+                markers.loaded_rows.insert(row);
+            }
+        }
+        markers
+    }
+    
+    /// Markers an earlier attempt left for a batch
+    pub(crate) async fn batch_markers(&self, key: String) -> Result<BatchMarkers> {
+        let rows = self.execute_graph_query(&Self::batch_markers_lookup(&key)).await?;
+        Ok(Self::parse_batch_markers(key, &rows))
+    }
+    
+    /// Whether the result of `batch_marker_lookup` has a positive count. An empty or
+    /// non-numeric result counts as not committed, so the batch is sent again.
+    pub(crate) fn batch_marker_found(rows: &[Vec<FalkorValue>]) -> bool {
+        rows.first()
+            .and_then(|row| row.first())
+            .and_then(|count| Self::falkor_value_to_string(count).parse::<i64>().ok())
+            .is_some_and(|count| count > 0)
+    }
+    
+    /// Whether the marker of a batch exists, i.e. the batch has been committed
+    pub(crate) async fn batch_committed(&self, key: &str) -> Result<bool> {
+        let rows = self.execute_graph_query(&Self::batch_marker_lookup(key)).await?;
+        Ok(Self::batch_marker_found(&rows))
+    }
+    
+    /// Whether any `--vector-column` is present in the given row
+    pub(crate) fn has_vector_columns(&self, row: Option<&HashMap<String, String>>) -> bool {
+        !self.options.topology_only && row.is_some_and(|row| {
+            self.options.vector_columns.keys().any(|column| row.contains_key(column))
+        })
+    }
+    
+    /// Parse the row's `--vector-column` values into `column: vecf32([...])` map entries.
+    /// Elements may be separated by `,`, `;`, `|` or whitespace and wrapped in `[]`; empty
+    /// cells are skipped. A non-numeric element or wrong element count is an error.
+    pub(crate) fn vector_entries(&self, row: &HashMap<String, String>, file_name: &str, raw: &RawCsvRecord) -> Result<Vec<String>> {
+        Ok(self.vector_values(row, file_name, raw)?.into_iter()
+            .map(|(column, elements)| {
+                let values: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                format!("{}: vecf32([{}])", column, values.join(", "))
+            })
+            .collect())
+    }
+    
+    /// Parsed `--vector-column` values of a row, checked against their dimension
+    pub(crate) fn vector_values(&self, row: &HashMap<String, String>, file_name: &str, raw: &RawCsvRecord) -> Result<Vec<(String, Vec<f32>)>> {
+        let mut entries = Vec::new();
+        if self.options.topology_only {
+            return Ok(entries);
+        }
+        
+        for (column, &dim) in &self.options.vector_columns {
+            let value = row.get(column).map(|v| v.trim()).unwrap_or("");
+            let value = value.trim_start_matches('[').trim_end_matches(']');
+            if value.is_empty() {
+                continue;
+            }
+            
+            let elements = value
+                .split(|c: char| c == ',' || c == ';' || c == '|' || c.is_whitespace())
+                .filter(|e| !e.is_empty())
+                .map(|e| e.parse::<f32>().map_err(|_| anyhow!(
+                    "Invalid vector element {:?} in column '{}' of {} line {}", e, column, file_name, raw.line
+                )))
+                .collect::<Result<Vec<f32>>>()?;
+            if elements.len() != dim {
+                return Err(anyhow!(
+                    "Vector column '{}' of {} line {} has {} elements, expected dimension {}",
+                    column, file_name, raw.line, elements.len(), dim
+                ));
+            }
+            
+            entries.push((column.clone(), elements));
+        }
+        
+        Ok(entries)
+    }
+    
+    /// `key: literal` entries and `--append-list` appends (`var.key = coalesce(var.key, []) + literal`)
+    /// of a row for its per-row fallback query. `skip` picks out columns that aren't properties.
+    /// Values are typed like the batch path, so a fallback doesn't turn numbers into strings.
+    pub(crate) fn fallback_properties(&self, var: &str, row: &HashMap<String, String>, raw: &RawCsvRecord,
+                                      positions: &HashMap<String, usize>, column_types: Option<&HashMap<String, ColumnType>>,
+                                      skip: impl Fn(&str) -> bool) -> (Vec<String>, Vec<String>) {
+        let mut properties = Vec::new();
+        let mut append_updates = Vec::new();
+        if self.options.topology_only {
+            return (properties, append_updates);
+        }
+        for (key, value) in Self::ordered_entries(row, positions) {
+            if value.is_empty() || skip(key) || self.options.vector_columns.contains_key(key) {
+                continue;
+            }
+            let quoted = raw.quoted_columns.contains(key);
+            let Some(value) = Self::property_value(value, quoted, &self.options) else {
+                continue;
+            };
+            let literal = Self::property_literal(key, &value, quoted, &self.options, column_types);
+            if self.options.append_list_columns.contains(key) {
+                append_updates.push(format!("{v}.{k} = coalesce({v}.{k}, []) + {l}", v = var, k = key, l = literal));
+            } else if !self.is_omitted_default(key, &value, quoted) {
+                properties.push(format!("{}: {}", key, literal));
+            }
+        }
+        (properties, append_updates)
+    }
+    
+    /// Columns configured with `--append-list` that are present in the given row
+    pub(crate) fn append_list_columns(&self, row: Option<&HashMap<String, String>>) -> Vec<String> {
+        match row {
+            Some(row) => self.options.append_list_columns.iter()
+                .filter(|column| row.contains_key(column.as_str()))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+    
+    /// Build the `SET` assignments that append `row.append.<col>` to existing list properties.
+    ///
+    /// A missing value leaves the property untouched; otherwise the incoming value is added to
+    /// the end of the current list (a scalar becomes a single new element).
+    pub(crate) fn append_list_set_clause(var: &str, columns: &[String]) -> String {
+        columns.iter()
+            .map(|column| format!(
+                ", {v}.{c} = CASE WHEN row.append.{c} IS NULL THEN {v}.{c} ELSE coalesce({v}.{c}, []) + row.append.{c} END",
+                v = var, c = column
+            ))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn batch_key_is_stable_and_distinct() {
+        let key = FalkorDBCSVLoader::batch_key("nodes_Person.csv", 3, 5000);
+        assert_eq!(key, "nodes_Person.csv#5000#3");
+        assert_eq!(key, FalkorDBCSVLoader::batch_key("nodes_Person.csv", 3, 5000));
+        assert_ne!(key, FalkorDBCSVLoader::batch_key("nodes_Person.csv", 4, 5000));
+        // A different batch size splits the file differently, so it must not match
+        assert_ne!(key, FalkorDBCSVLoader::batch_key("nodes_Person.csv", 3, 1000));
+        assert_ne!(key, FalkorDBCSVLoader::batch_key("nodes_Company.csv", 3, 5000));
+    }
+    
+    #[test]
+    fn batch_marker_clause_text() {
+        assert_eq!(
+            FalkorDBCSVLoader::batch_marker_clause("nodes_Person.csv#5000#3"),
+            " WITH count(*) AS batch_rows CREATE (:_LoaderBatch {key: 'nodes_Person.csv#5000#3'})"
+        );
+        assert_eq!(
+            FalkorDBCSVLoader::batch_marker_clause("o'brien.csv#10#0"),
+            " WITH count(*) AS batch_rows CREATE (:_LoaderBatch {key: 'o\\'brien.csv#10#0'})"
+        );
+    }
+    
+    #[test]
+    fn batch_markers_separate_the_batch_from_fallback_rows() {
+        let key = FalkorDBCSVLoader::batch_key("edges_KNOWS.csv", 1, 1000);
+        assert_eq!(
+            FalkorDBCSVLoader::batch_markers_lookup(&key),
+            "MATCH (m:_LoaderBatch) WHERE m.key = 'edges_KNOWS.csv#1000#1' OR m.key STARTS WITH 'edges_KNOWS.csv#1000#1#' RETURN m.key"
+        );
+        let marker = |key: String| vec![FalkorValue::String(key)];
+        
+        // A fallback that stopped after rows 0 and 2 of the batch
+        let rows = [
+            marker(FalkorDBCSVLoader::row_marker_key(&key, 0)),
+            marker(FalkorDBCSVLoader::row_marker_key(&key, 2)),
+        ];
+        let markers = FalkorDBCSVLoader::parse_batch_markers(key.clone(), &rows);
+        assert!(!markers.committed);
+        assert_eq!(markers.loaded_rows, HashSet::from([0, 2]));
+        
+        let markers = FalkorDBCSVLoader::parse_batch_markers(key.clone(), &[marker(key.clone())]);
+        assert!(markers.committed);
+        assert!(markers.loaded_rows.is_empty());
+        
+        // Batch 12 shares the prefix of batch 1 but not its row separator
+        let other = FalkorDBCSVLoader::batch_key("edges_KNOWS.csv", 12, 1000);
+        let markers = FalkorDBCSVLoader::parse_batch_markers(key, &[marker(other.clone()), marker(format!("{}#3", other))]);
+        assert!(!markers.committed);
+        assert!(markers.loaded_rows.is_empty());
+    }
+    
+    #[test]
+    fn batch_marker_found_parses_count() {
+        assert!(FalkorDBCSVLoader::batch_marker_found(&[vec![FalkorValue::I64(2)]]));
+        assert!(!FalkorDBCSVLoader::batch_marker_found(&[vec![FalkorValue::I64(0)]]));
+        assert!(!FalkorDBCSVLoader::batch_marker_found(&[vec![FalkorValue::None]]));
+        assert!(!FalkorDBCSVLoader::batch_marker_found(&[vec![]]));
+        assert!(!FalkorDBCSVLoader::batch_marker_found(&[]));
+    }
+}
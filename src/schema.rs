@@ -0,0 +1,696 @@
+//! Indexes and constraints.
+
+use anyhow::{anyhow, Result};
+use falkordb::FalkorValue;
+use log::{error, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use std::sync::atomic::Ordering;
+
+use crate::files::{CsvRows, RawCsvRecord};
+use crate::loader::FalkorDBCSVLoader;
+
+impl FalkorDBCSVLoader {
+    /// Execute a FalkorDB constraint command with error handling
+    /// Note: For now, we'll use a simple query-based approach for constraint creation
+    /// as the falkordb-rs library may handle constraints through graph queries
+    async fn execute_constraint(&self, label: &str, properties: &[&str], constraint_type: &str, entity_type: &str) -> Result<()> {
+        // Check if we should terminate
+        if self.terminate_on_error.load(Ordering::Relaxed) {
+            return Err(anyhow!("Loading terminated due to previous errors"));
+        }
+        
+        let mut graph = self.client().select_graph(&self.graph_name);
+        
+        // Build constraint query - this might need adjustment based on FalkorDB's constraint syntax
+        let label = self.label_identifier(label);
+        let query = if constraint_type.to_uppercase().contains("UNIQUE") && entity_type.to_uppercase() == "NODE" {
+            if properties.len() == 1 {
+                format!("CREATE CONSTRAINT FOR (n:{}) REQUIRE n.{} IS UNIQUE", label, properties[0])
+            } else {
+                let prop_list = properties.iter().map(|p| format!("n.{}", p)).collect::<Vec<_>>().join(", ");
+                format!("CREATE CONSTRAINT FOR (n:{}) REQUIRE ({}) IS UNIQUE", label, prop_list)
+            }
+        } else {
+            return Err(anyhow!("Unsupported constraint type: {} for entity type: {}", constraint_type, entity_type));
+        };
+        if self.options.dry_run {
+            self.log_dry_run_query(&self.graph_name, &query);
+            return Ok(());
+        }
+        
+        let _result = graph.query(&self.tag_query(&query))
+            .execute()
+            .await
+            .map_err(|e| {
+                if Self::is_connection_error(&e) {
+                    error!("❌ Connection error in constraint creation: {:?}", e);
+                    self.terminate_on_error.store(true, Ordering::Relaxed);
+                } else if self.options.fail_fast {
+                    self.terminate_on_error.store(true, Ordering::Relaxed);
+                }
+                anyhow!("Constraint creation failed: {:?}", e)
+            })?;
+        Ok(())
+    }
+    
+    /// Poll `db.indexes()` until no index is still being populated, or `timeout` passes.
+    /// A timeout only warns: edges still load, just without the index speedup.
+    pub(crate) async fn wait_for_indexes(&self, timeout: Duration) -> Result<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(500);
+        let started = Instant::now();
+        let query = "CALL db.indexes() YIELD label, properties, status RETURN label, properties, status";
+        
+        for poll in 0.. {
+            let pending: Vec<String> = self.execute_graph_query(query).await?
+                .iter()
+                .filter(|row| !matches!(row.get(2), Some(FalkorValue::String(status)) if status.eq_ignore_ascii_case("OPERATIONAL")))
+                .map(|row| {
+                    let label = row.first().map(Self::falkor_value_to_string).unwrap_or_default();
+                    let properties = match row.get(1) {
+                        Some(FalkorValue::Array(values)) => values.iter().map(Self::falkor_value_to_string).collect::<Vec<_>>().join(", "),
+                        _ => String::new(),
+                    };
+                    format!("{}({})", label, properties)
+                })
+                .collect();
+            
+            if pending.is_empty() {
+                info!("✅ All indexes operational (waited {:?})", started.elapsed());
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                warn!("⚠️ Gave up waiting for indexes after {:?}, still populating: {:?}", started.elapsed(), pending);
+                return Ok(());
+            }
+            // Log every 10 seconds rather than on every poll
+            if poll % 20 == 0 {
+                info!("⏳ Waiting for {} indexes to finish populating: {:?}", pending.len(), pending);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+    
+    /// Create ID indexes for all node labels
+    pub async fn create_id_indexes_for_all_labels(&self) -> Result<()> {
+        if !self.csv_dir.exists() {
+            return Ok(());
+        }
+        
+        info!("🔧 Creating ID indexes for all node labels...");
+        
+        let csv_files = self.list_selected_csv_files()?;
+        let mut created_count = 0;
+        
+        // Collect distinct labels first so sharded part files only create one index per label
+        let mut labels = BTreeSet::new();
+        for file_path in &csv_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            
+            // Extract labels from filename or --label-column; a multi-label indexes each part
+            if self.is_node_file(&file_name) {
+                for label in self.node_file_labels(file_path)? {
+                    labels.extend(self.label_parts(&label).into_iter().map(str::to_string));
+                }
+            }
+        }
+        
+        for label in &labels {
+            let query = format!("CREATE INDEX ON :{}(id)", self.label_identifier(label));
+            info!("  Creating ID index: {}", query);
+            
+            match self.execute_graph_query(&query).await {
+                Ok(_) => created_count += 1,
+                Err(e) => {
+                    let error_msg = e.to_string().to_lowercase();
+                    if error_msg.contains("already exists") || 
+                       error_msg.contains("equivalent") || 
+                       error_msg.contains("already indexed") || 
+                       error_msg.contains("index exists") {
+                        // Silently skip - index already exists
+                    } else {
+                        error!("  ❌ Error creating ID index on {}.id: {}", label, e);
+                    }
+                }
+            }
+        }
+        
+        if created_count > 0 {
+            info!("✅ Created {} ID indexes", created_count);
+        } else {
+            info!("  No new ID indexes created");
+        }
+        
+        Ok(())
+    }
+    
+    /// Create indexes from indexes.csv file
+    pub async fn create_indexes_from_csv(&self) -> Result<()> {
+        let indexes_file = self.csv_dir.join("indexes.csv");
+        if !Self::csv_file_exists(&indexes_file) {
+            warn!("⚠️ No indexes.csv file found, skipping index creation");
+            return Ok(());
+        }
+        
+        info!("🔧 Creating indexes from CSV...");
+        let records = self.read_csv_file(&indexes_file)?;
+        
+        let mut created_count = 0;
+        let mut skipped_count = 0;
+        
+        for record in records {
+            let empty_string = String::new();
+            let labels = record.get("labels").unwrap_or(&empty_string).trim();
+            let properties = record.get("properties").unwrap_or(&empty_string).trim();
+            let uniqueness = record.get("uniqueness").unwrap_or(&empty_string);
+            let index_type = record.get("type").unwrap_or(&empty_string).to_uppercase();
+            
+            // Skip system indexes, unique constraints, and indexes without labels/properties
+            if labels.is_empty() || properties.is_empty() || 
+               index_type == "LOOKUP" || uniqueness == "UNIQUE" {
+                skipped_count += 1;
+                continue;
+            }
+            
+            // Split labels and properties
+            let label_list: Vec<&str> = labels.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            let prop_list: Vec<&str> = properties.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            
+            // Full-text indexes are created by procedure call, one per label covering all properties
+            if index_type == "FULLTEXT" {
+                let language = record.get("language").map(|s| s.trim()).unwrap_or("");
+                let stopwords: Vec<&str> = record.get("stopwords")
+                    .map(|s| s.split(';').map(|w| w.trim()).filter(|w| !w.is_empty()).collect())
+                    .unwrap_or_default();
+                
+                for label in &label_list {
+                    let query = Self::fulltext_index_query(&self.graph_label(label), &prop_list, language, &stopwords);
+                    info!("  Creating: {}", query);
+                    
+                    match self.execute_graph_query(&query).await {
+                        Ok(_) => created_count += 1,
+                        Err(e) => {
+                            let error_msg = e.to_string().to_lowercase();
+                            if !error_msg.contains("already exists") && !error_msg.contains("already indexed") {
+                                error!("  ❌ Error creating full-text index on {}: {}", label, e);
+                            }
+                        }
+                    }
+                }
+                continue;
+            }
+            
+            // Vector indexes are created by procedure call, one per label-property combination
+            if index_type == "VECTOR" {
+                let settings = Self::vector_index_settings(
+                    record.get("dimension").map(String::as_str).unwrap_or(""),
+                    record.get("similarity").map(String::as_str),
+                );
+                let (dimension, similarity) = match settings {
+                    Ok(settings) => settings,
+                    Err(e) => {
+                        error!("  ❌ Vector index on {} {}", labels, e);
+                        skipped_count += 1;
+                        continue;
+                    }
+                };
+                
+                for label in &label_list {
+                    for prop in &prop_list {
+                        let query = Self::vector_index_query(&self.graph_label(label), prop, dimension, &similarity);
+                        info!("  Creating: {}", query);
+                        
+                        match self.execute_graph_query(&query).await {
+                            Ok(_) => created_count += 1,
+                            Err(e) => {
+                                let error_msg = e.to_string().to_lowercase();
+                                if !error_msg.contains("already exists") && !error_msg.contains("already indexed") {
+                                    error!("  ❌ Error creating vector index on {}.{}: {}", label, prop, e);
+                                }
+                            }
+                        }
+                    }
+                }
+                continue;
+            }
+            
+            // Create index for each label-property combination
+            for label in &label_list {
+                for prop in &prop_list {
+                    let query = format!("CREATE INDEX ON :{}({})", self.label_identifier(label), prop);
+                    info!("  Creating: {}", query);
+                    
+                    match self.execute_graph_query(&query).await {
+                        Ok(_) => created_count += 1,
+                        Err(e) => {
+                            let error_msg = e.to_string().to_lowercase();
+                            if error_msg.contains("already exists") || 
+                               error_msg.contains("equivalent") || 
+                               error_msg.contains("already indexed") || 
+                               error_msg.contains("index exists") {
+                                // Silently skip - index already exists
+                            } else {
+                                error!("  ❌ Error creating index on {}.{}: {}", label, prop, e);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        info!("✅ Created {} indexes from CSV, skipped {}", created_count, skipped_count);
+        Ok(())
+    }
+    
+    /// Build the `db.idx.fulltext.createNodeIndex` call for a label. Without language or
+    /// stopwords the label is passed as a plain string, otherwise as an options map.
+    fn fulltext_index_query(label: &str, properties: &[&str], language: &str, stopwords: &[&str]) -> String {
+        let label_arg = if language.is_empty() && stopwords.is_empty() {
+            Self::string_literal(label)
+        } else {
+            let mut options = vec![format!("label: {}", Self::string_literal(label))];
+            if !language.is_empty() {
+                options.push(format!("language: {}", Self::string_literal(language)));
+            }
+            if !stopwords.is_empty() {
+                let words: Vec<String> = stopwords.iter().map(|w| Self::string_literal(w)).collect();
+                options.push(format!("stopwords: [{}]", words.join(", ")));
+            }
+            format!("{{{}}}", options.join(", "))
+        };
+        
+        let fields: Vec<String> = properties.iter().map(|p| Self::string_literal(p)).collect();
+        format!("CALL db.idx.fulltext.createNodeIndex({}, {})", label_arg, fields.join(", "))
+    }
+    
+    /// Dimension and similarity function of a `VECTOR` row in `indexes.csv`. The dimension must
+    /// be a positive integer; the similarity defaults to `euclidean` and may also be `cosine`.
+    fn vector_index_settings(dimension: &str, similarity: Option<&str>) -> std::result::Result<(usize, String), String> {
+        let dimension = match dimension.trim().parse::<usize>() {
+            Ok(parsed) if parsed > 0 => parsed,
+            _ => return Err(format!("needs a positive 'dimension', got {:?}", dimension.trim())),
+        };
+        let similarity = similarity
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "euclidean".to_string());
+        if similarity != "euclidean" && similarity != "cosine" {
+            return Err(format!("has unknown similarity function '{}' (expected euclidean or cosine)", similarity));
+        }
+        Ok((dimension, similarity))
+    }
+    
+    /// Build the `db.idx.vector.createNodeIndex` call for one label and property
+    fn vector_index_query(label: &str, property: &str, dimension: usize, similarity: &str) -> String {
+        format!(
+            "CALL db.idx.vector.createNodeIndex({{label: {}, attribute: {}, dimension: {}, similarityFunction: {}}})",
+            Self::string_literal(label), Self::string_literal(property), dimension, Self::string_literal(similarity)
+        )
+    }
+    
+    /// Create supporting indexes for constraints
+    pub async fn create_supporting_indexes_for_constraints(&self) -> Result<()> {
+        let constraints_file = self.csv_dir.join("constraints.csv");
+        if !Self::csv_file_exists(&constraints_file) {
+            return Ok(());
+        }
+        
+        info!("🔧 Creating supporting indexes for constraints...");
+        let records = self.read_csv_file(&constraints_file)?;
+        
+        if records.is_empty() {
+            return Ok(());
+        }
+        
+        let mut created_count = 0;
+        
+        for record in records {
+            let empty_string = String::new();
+            let labels = record.get("labels").unwrap_or(&empty_string).trim();
+            let properties = record.get("properties").unwrap_or(&empty_string).trim();
+            let constraint_type = record.get("type").unwrap_or(&empty_string).to_uppercase();
+            
+            // Only create indexes for UNIQUE constraints
+            if labels.is_empty() || properties.is_empty() || !constraint_type.contains("UNIQUE") {
+                continue;
+            }
+            
+            // Split labels and properties
+            let label_list: Vec<&str> = labels.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            let prop_list: Vec<&str> = properties.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            
+            // Create supporting index for each label
+            for label in &label_list {
+                let query = if prop_list.len() == 1 {
+                    format!("CREATE INDEX FOR (n:{}) ON (n.{})", self.label_identifier(label), prop_list[0])
+                } else {
+                    let prop_str: Vec<String> = prop_list.iter()
+                        .map(|prop| format!("n.{}", prop))
+                        .collect();
+                    format!("CREATE INDEX FOR (n:{}) ON ({})", self.label_identifier(label), prop_str.join(", "))
+                };
+                
+                info!("  Creating supporting index: {}", query);
+                
+                match self.execute_graph_query(&query).await {
+                    Ok(_) => created_count += 1,
+                    Err(e) => {
+                        let error_msg = e.to_string().to_lowercase();
+                        if error_msg.contains("already indexed") || 
+                           error_msg.contains("already exists") || 
+                           error_msg.contains("equivalent") || 
+                           error_msg.contains("index exists") {
+                            // Silently skip - supporting index already exists
+                        } else {
+                            error!("  ❌ Error creating supporting index for {}({}): {}", 
+                                   label, prop_list.join(", "), e);
+                        }
+                    }
+                }
+            }
+        }
+        
+        if created_count > 0 {
+            info!("✅ Created {} supporting indexes", created_count);
+        }
+        
+        Ok(())
+    }
+    
+    /// Create constraints from constraints.csv file
+    pub async fn create_constraints_from_csv(&self) -> Result<()> {
+        let constraints_file = self.csv_dir.join("constraints.csv");
+        if !Self::csv_file_exists(&constraints_file) {
+            warn!("⚠️ No constraints.csv file found, skipping constraint creation");
+            return Ok(());
+        }
+        
+        info!("🔒 Creating constraints...");
+        let records = self.read_csv_file(&constraints_file)?;
+        
+        if records.is_empty() {
+            info!("  No constraints to create");
+            return Ok(());
+        }
+        
+        let existing = self.existing_constraints().await;
+        let mut created_count = 0;
+        let mut skipped_count = 0;
+        let mut pre_existing = Vec::new();
+        
+        for record in records {
+            let empty_string = String::new();
+            let labels = record.get("labels").unwrap_or(&empty_string).trim();
+            let properties = record.get("properties").unwrap_or(&empty_string).trim();
+            let constraint_type = record.get("type").unwrap_or(&empty_string).to_uppercase();
+            let entity_type = record.get("entity_type").map_or("NODE", |v| v).to_uppercase();
+            
+            // Skip constraints without labels/properties
+            if labels.is_empty() || properties.is_empty() {
+                skipped_count += 1;
+                continue;
+            }
+            
+            // Split labels and properties
+            let label_list: Vec<&str> = labels.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            let prop_list: Vec<&str> = properties.split(';')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            
+            // Create constraint for each label
+            for label in &label_list {
+                if constraint_type.contains("UNIQUE") {
+                    let key = Self::constraint_key("UNIQUE", &entity_type, &self.graph_label(label), &prop_list);
+                    if existing.as_ref().is_some_and(|existing| existing.contains(&key)) {
+                        info!("  ✓ UNIQUE constraint on {}({}) already exists", label, prop_list.join(", "));
+                        pre_existing.push(format!("{}({})", label, prop_list.join(", ")));
+                        continue;
+                    }
+                    
+                    match self.execute_constraint(label, &prop_list, &constraint_type, &entity_type).await {
+                        Ok(()) => {
+                            created_count += 1;
+                            info!("  ✅ Successfully created UNIQUE constraint on {}({})", 
+                                  label, prop_list.join(", "));
+                        }
+                        Err(e) => {
+                            let error_msg = e.to_string().to_lowercase();
+                            if error_msg.contains("already exists") || 
+                               error_msg.contains("constraint already exists") {
+                                warn!("  ⚠️ Constraint on {}({}) already exists, skipping", 
+                                      label, prop_list.join(", "));
+                            } else {
+                                error!("  ❌ Error creating constraint on {}({}): {}", 
+                                       label, prop_list.join(", "), e);
+                                skipped_count += 1;
+                            }
+                        }
+                    }
+                } else {
+                    // Handle other constraint types if necessary
+                    warn!("  ⚠️ Constraint type '{}' not supported by this loader, skipping {}.{:?}", 
+                          constraint_type, label, prop_list);
+                    skipped_count += 1;
+                }
+            }
+        }
+        
+        if created_count > 0 {
+            info!("✅ Created {} constraints", created_count);
+        }
+        if !pre_existing.is_empty() {
+            info!("✓ {} constraints already existed: {}", pre_existing.len(), pre_existing.join(", "));
+        }
+        if skipped_count > 0 {
+            warn!("⚠️ Skipped {} constraints", skipped_count);
+        }
+        
+        Ok(())
+    }
+    
+    /// Normalised identity of a constraint: type, entity type, label and sorted properties
+    fn constraint_key(constraint_type: &str, entity_type: &str, label: &str, properties: &[&str]) -> (String, String, String, Vec<String>) {
+        let mut properties: Vec<String> = properties.iter().map(|p| p.to_string()).collect();
+        properties.sort();
+        (constraint_type.to_uppercase(), entity_type.to_uppercase(), label.to_string(), properties)
+    }
+    
+    /// Constraints already defined on the graph according to `db.constraints()`, or `None` if
+    /// they can't be listed (creation then falls back to tolerating "already exists" errors)
+    async fn existing_constraints(&self) -> Option<HashSet<(String, String, String, Vec<String>)>> {
+        let query = "CALL db.constraints() YIELD type, label, properties, entitytype RETURN type, label, properties, entitytype";
+        let rows = match self.execute_graph_query(query).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("⚠️ Could not list existing constraints, relying on creation errors instead: {}", e);
+                return None;
+            }
+        };
+        
+        let constraints = rows.iter()
+            .filter(|row| row.len() >= 4)
+            .map(|row| {
+                let properties: Vec<String> = match &row[2] {
+                    FalkorValue::Array(values) => values.iter().map(Self::falkor_value_to_string).collect(),
+                    other => vec![Self::falkor_value_to_string(other)],
+                };
+                let properties: Vec<&str> = properties.iter().map(|p| p.as_str()).collect();
+                Self::constraint_key(
+                    &Self::falkor_value_to_string(&row[0]),
+                    &Self::falkor_value_to_string(&row[3]),
+                    &Self::falkor_value_to_string(&row[1]),
+                    &properties,
+                )
+            })
+            .collect();
+        Some(constraints)
+    }
+    
+    /// Node properties matching the source and target of a relationship type (`--endpoint-keys`)
+    pub(crate) fn endpoint_keys(&self, rel_type: &str) -> (&str, &str) {
+        match self.options.endpoint_keys.get(rel_type) {
+            Some((source_key, target_key)) => (source_key, target_key),
+            None => ("id", "id"),
+        }
+    }
+    
+    /// Every (label, property) pair covered by an index, from `db.indexes()`
+    async fn indexed_properties(&self) -> Result<HashSet<(String, String)>> {
+        Ok(self.execute_graph_query("CALL db.indexes() YIELD label, properties RETURN label, properties").await?
+            .iter()
+            .flat_map(|row| {
+                let label = row.first().map(Self::falkor_value_to_string).unwrap_or_default();
+                let properties = match row.get(1) {
+                    Some(FalkorValue::Array(values)) => values.iter().map(Self::falkor_value_to_string).collect(),
+                    _ => Vec::new(),
+                };
+                properties.into_iter().map(move |property| (label.clone(), property))
+            })
+            .collect())
+    }
+    
+    /// `--require-indexes` precondition for the edge phase: every node label must have an `id`
+    /// index, and the labels in each edge file's first row an index on the file's endpoint keys.
+    /// Fails listing all missing indexes instead of letting edge matching fall back to scans.
+    pub(crate) async fn require_edge_indexes(&self, node_files: &[PathBuf], edge_files: &[PathBuf]) -> Result<()> {
+        if edge_files.is_empty() {
+            return Ok(());
+        }
+        info!("🔎 Checking that the indexes edge matching needs exist...");
+        let mut required: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+        for node_file in node_files {
+            let file_name = node_file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            for label in self.node_file_labels(node_file)? {
+                required.entry((self.graph_label(self.primary_label(&label)), "id".to_string())).or_default().insert(file_name.clone());
+            }
+        }
+        for edge_file in edge_files {
+            let file_name = edge_file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if !self.is_edge_file(&file_name) {
+                continue;
+            }
+            let rel_type = Self::entity_name_from_file_name(&file_name, "edges_").unwrap_or(&file_name);
+            let Some(first_row) = Self::csv_reader(edge_file, self.options.delimiter.0)?.deserialize::<HashMap<String, String>>().next().transpose()? else {
+                continue;
+            };
+            let (source_key, target_key) = self.endpoint_keys(rel_type);
+            for (label_column, key) in [("source_label", source_key), ("target_label", target_key)] {
+                let raw_label = first_row.get(label_column).map_or("", |l| l.trim());
+                let label = self.label_mapping.get(raw_label).map_or(raw_label, |l| l.as_str());
+                let label = self.primary_label(label);
+                if !label.is_empty() {
+                    required.entry((self.graph_label(label), key.to_string())).or_default().insert(file_name.clone());
+                }
+            }
+        }
+        
+        let indexed = self.indexed_properties().await?;
+        let missing: Vec<String> = required.iter()
+            .filter(|(pair, _)| !indexed.contains(*pair))
+            .map(|((label, key), files)| format!("{}({}) for {}", label, key, files.iter().cloned().collect::<Vec<_>>().join(", ")))
+            .collect();
+        if !missing.is_empty() {
+            for index in &missing {
+                error!("  ❌ Missing index {}", index);
+            }
+            return Err(anyhow!(
+                "{} index(es) needed to match edge endpoints are missing, so edge loading would scan every node per row; \
+                 check the index creation errors above, add them to indexes.csv, or drop --skip-id-index",
+                missing.len()
+            ));
+        }
+        info!("✅ All {} required indexes exist", required.len());
+        Ok(())
+    }
+    
+    /// Check that every (label, key) pair an edge file matches endpoints on is indexed, so
+    /// `--endpoint-keys` can't silently turn each batch into label scans. Rows from
+    /// `reversed_start` on had their endpoints swapped and use the keys the other way round.
+    /// Pairs in `checked` were found indexed for an earlier chunk and are not looked up again.
+    pub(crate) async fn check_endpoint_key_indexes(&self, file_name: &str, rows: &CsvRows, raw_records: &[RawCsvRecord], reversed_start: usize,
+                                        (source_key, target_key): (&str, &str), checked: &mut BTreeSet<(String, String)>) -> Result<()> {
+        let mut required = BTreeSet::new();
+        for (i, row) in rows.iter().enumerate() {
+            let keys = if i >= reversed_start { [target_key, source_key] } else { [source_key, target_key] };
+            for (label_column, key) in ["source_label", "target_label"].into_iter().zip(keys) {
+                let raw_label = row.get(label_column).map_or("", |l| l.trim());
+                let label = self.label_mapping.get(raw_label).map_or(raw_label, |l| l.as_str());
+                let label = self.primary_label(label);
+                if label.is_empty() {
+                    return Err(anyhow!("--endpoint-keys for {} needs source_label and target_label values (line {} has none)",
+                                       file_name, raw_records[i].line));
+                }
+                if key != "id" {
+                    required.insert((self.graph_label(label), key.to_string()));
+                }
+            }
+        }
+        required.retain(|pair| !checked.contains(pair));
+        if required.is_empty() {
+            return Ok(());
+        }
+        
+        let indexed = self.indexed_properties().await?;
+        let missing: Vec<String> = required.iter()
+            .filter(|pair| !indexed.contains(*pair))
+            .map(|(label, key)| format!("{}({})", label, key))
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!("--endpoint-keys for {} match on unindexed properties {}; add them to indexes.csv",
+                               file_name, missing.join(", ")));
+        }
+        checked.extend(required);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn fulltext_index_query_plain_label() {
+        assert_eq!(
+            FalkorDBCSVLoader::fulltext_index_query("Movie", &["title", "plot"], "", &[]),
+            "CALL db.idx.fulltext.createNodeIndex('Movie', 'title', 'plot')"
+        );
+    }
+    
+    #[test]
+    fn fulltext_index_query_options_map() {
+        assert_eq!(
+            FalkorDBCSVLoader::fulltext_index_query("Movie", &["title"], "german", &["der", "die"]),
+            "CALL db.idx.fulltext.createNodeIndex({label: 'Movie', language: 'german', stopwords: ['der', 'die']}, 'title')"
+        );
+        assert_eq!(
+            FalkorDBCSVLoader::fulltext_index_query("Movie", &["title"], "english", &[]),
+            "CALL db.idx.fulltext.createNodeIndex({label: 'Movie', language: 'english'}, 'title')"
+        );
+        assert_eq!(
+            FalkorDBCSVLoader::fulltext_index_query("Movie", &["title"], "", &["a"]),
+            "CALL db.idx.fulltext.createNodeIndex({label: 'Movie', stopwords: ['a']}, 'title')"
+        );
+    }
+    
+    #[test]
+    fn vector_index_query_text() {
+        assert_eq!(
+            FalkorDBCSVLoader::vector_index_query("Doc", "embedding", 384, "cosine"),
+            "CALL db.idx.vector.createNodeIndex({label: 'Doc', attribute: 'embedding', dimension: 384, similarityFunction: 'cosine'})"
+        );
+    }
+    
+    #[test]
+    fn vector_index_settings_validation() {
+        assert_eq!(FalkorDBCSVLoader::vector_index_settings("384", None), Ok((384, "euclidean".to_string())));
+        assert_eq!(FalkorDBCSVLoader::vector_index_settings(" 3 ", Some("")), Ok((3, "euclidean".to_string())));
+        assert_eq!(FalkorDBCSVLoader::vector_index_settings("3", Some(" Cosine ")), Ok((3, "cosine".to_string())));
+        
+        for dimension in ["0", "", "abc", "-4", "1.5"] {
+            let error = FalkorDBCSVLoader::vector_index_settings(dimension, None).unwrap_err();
+            assert!(error.starts_with("needs a positive 'dimension'"), "{:?}: {}", dimension, error);
+        }
+        assert_eq!(
+            FalkorDBCSVLoader::vector_index_settings("3", Some("manhattan")),
+            Err("has unknown similarity function 'manhattan' (expected euclidean or cosine)".to_string())
+        );
+    }
+}
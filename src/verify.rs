@@ -0,0 +1,620 @@
+//! Checks after a load, graph statistics and graph comparison.
+
+use anyhow::{anyhow, Result};
+use falkordb::FalkorValue;
+use log::{error, info, warn};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::loader::FalkorDBCSVLoader;
+use crate::metrics::Checksum;
+use crate::options::LoaderOptions;
+
+/// Number of nodes with exactly this set of labels
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelCount {
+    pub labels: Vec<String>,
+    pub count: i64,
+}
+
+/// Number of relationships of one type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RelTypeCount {
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    pub count: i64,
+}
+
+/// Node and relationship counts of a graph, as reported by `--stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GraphStats {
+    pub nodes: Vec<LabelCount>,
+    pub relationships: Vec<RelTypeCount>,
+}
+
+impl FalkorDBCSVLoader {
+    /// Verify node attributes for a specific node type
+    pub async fn verify_node_attributes(&self, label: &str, limit: usize) -> Result<()> {
+        let query = format!("MATCH (n:{}) RETURN n LIMIT {}", self.label_identifier(label), limit);
+        match self.execute_read_query_rows(&query).await {
+            Ok(rows) => {
+                info!("\n🔍 Sample {} nodes with their attributes:", label);
+                for row in rows {
+                    if let Some(FalkorValue::Node(node)) = row.first() {
+                        let properties: BTreeMap<&String, String> = node.properties.iter()
+                            .map(|(key, value)| (key, Self::falkor_value_to_string(value)))
+                            .collect();
+                        info!("  (:{}) {:?}", node.labels.join(":"), properties);
+                    }
+                }
+            }
+            Err(e) => {
+                error!("❌ Error verifying node attributes: {}", e);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Whether a stored property equals the CSV value it was loaded from, after the same
+    /// type coercion the loader applies (integers, then floats, otherwise strings). A stored
+    /// boolean can only come from `--infer-bools`.
+    pub(crate) fn stored_value_matches(value: &str, quoted: bool, stored: &FalkorValue) -> bool {
+        let as_string = quoted || Self::is_formatted_number(value);
+        match stored {
+            FalkorValue::Bool(b) => !quoted && Self::parse_bool(value) == Some(*b),
+            FalkorValue::I64(i) => !as_string && value.parse::<i64>().is_ok_and(|v| v == *i),
+            FalkorValue::F64(f) => !as_string && value.parse::<i64>().is_err()
+                && value.parse::<f64>().is_ok_and(|v| v == *f),
+            FalkorValue::String(s) => (as_string || value.parse::<f64>().is_err()) && s == value,
+            _ => false,
+        }
+    }
+    
+    /// Evenly spread indices of up to `sample_size` rows out of `len`
+    fn sample_indices(len: usize, sample_size: usize) -> Vec<usize> {
+        if sample_size >= len {
+            return (0..len).collect();
+        }
+        (0..sample_size).map(|i| i * len / sample_size).collect()
+    }
+    
+    /// Fetch the entity for one sampled row and compare its properties with the expected
+    /// `(key, value, quoted)` entries. `pattern` matches the entity as `e`. Logs and returns the
+    /// number of mismatches (a missing entity counts as one).
+    async fn verify_entity(&self, file_name: &str, line: u64, pattern: &str, expected: &[(String, String, bool)]) -> Result<usize> {
+        let query = format!(
+            "MATCH {} RETURN [{}] LIMIT 100",
+            pattern,
+            expected.iter().map(|(key, _, _)| format!("e.{}", key)).collect::<Vec<_>>().join(", ")
+        );
+        let candidates = self.execute_read_query_rows(&query).await?;
+        if candidates.is_empty() {
+            error!("  ❌ {} line {}: no entity found for {}", file_name, line, pattern);
+            return Ok(1);
+        }
+        
+        // Several entities can share an id in CREATE mode; any fully matching one will do
+        let mut first_mismatches = Vec::new();
+        for (i, candidate) in candidates.iter().enumerate() {
+            let stored = match candidate.first() {
+                Some(FalkorValue::Array(values)) => values.as_slice(),
+                _ => &[],
+            };
+            let mismatches: Vec<String> = expected.iter().enumerate()
+                .filter_map(|(j, (key, value, quoted))| {
+                    let stored = stored.get(j).unwrap_or(&FalkorValue::None);
+                    (!Self::stored_value_matches(value, *quoted, stored)).then(|| format!(
+                        "{} expected {} but stored {:?}", key, Self::typed_cypher_literal(value, *quoted), stored
+                    ))
+                })
+                .collect();
+            if mismatches.is_empty() {
+                return Ok(0);
+            }
+            if i == 0 {
+                first_mismatches = mismatches;
+            }
+        }
+        
+        for mismatch in &first_mismatches {
+            error!("  ❌ {} line {}: {}", file_name, line, mismatch);
+        }
+        Ok(first_mismatches.len())
+    }
+    
+    /// Read back a sample of rows from every node and edge file and check that the stored
+    /// properties equal the source values. Catches escaping and type-coercion bugs that row
+    /// counts alone would miss.
+    pub(crate) async fn verify_sampled_rows(&self, node_files: &[PathBuf], edge_files: &[PathBuf], sample_size: usize) -> Result<()> {
+        info!("\n🔍 Verifying up to {} sampled rows per file against the graph...", sample_size);
+        let edge_key_columns = ["source", "target", "type", "source_label", "target_label"];
+        let mut rows_checked = 0;
+        let mut mismatches = 0;
+        
+        for file_path in node_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let Some(raw_label) = Self::entity_name_from_file_name(&file_name, "nodes_") else {
+                continue;
+            };
+            let label_ident = self.label_identifier(&self.label_name(raw_label));
+            let (rows, raw_records) = self.read_csv_file_with_raw(file_path)?;
+            let append_columns = self.append_list_columns(rows.first());
+            
+            for index in Self::sample_indices(rows.len(), sample_size) {
+                let (row, raw) = (&rows[index], &raw_records[index]);
+                // Rows without an id only get one from their ordinal and can't be looked up here
+                let Some(id) = row.get("id").filter(|id| !id.is_empty()) else {
+                    continue;
+                };
+                let mut expected: Vec<(String, String, bool)> = row.iter()
+                    .filter(|(key, value)| !self.options.topology_only && !Self::is_node_key_column(key, &self.options)
+                        && !value.is_empty() && !Self::is_explicit_null(value, raw.quoted_columns.contains(*key), &self.options)
+                        && !self.is_omitted_default(key, value, raw.quoted_columns.contains(*key))
+                        && !append_columns.contains(key)
+                        && !self.options.vector_columns.contains_key(*key)
+                        && !Self::is_uncompared_column(key, &self.options))
+                    .map(|(key, value)| (key.clone(), value.clone(), raw.quoted_columns.contains(key)))
+                    .collect();
+                expected.sort();
+                
+                let pattern = format!("(e:{} {{id: {}}})", label_ident,
+                                      Self::typed_cypher_literal(id, raw.quoted_columns.contains("id")));
+                mismatches += self.verify_entity(&file_name, raw.line, &pattern, &expected).await?;
+                rows_checked += 1;
+            }
+        }
+        
+        for file_path in edge_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let Some(rel_type) = Self::entity_name_from_file_name(&file_name, "edges_") else {
+                continue;
+            };
+            let rel_type_ident = self.rel_type_identifier(rel_type);
+            let (mut rows, raw_records) = self.read_csv_file_with_raw(file_path)?;
+            if self.options.edges_by_ordinal {
+                self.resolve_edge_ordinals(&mut rows);
+            }
+            let append_columns = self.append_list_columns(rows.first());
+            let weight_column = self.options.weight_column.as_deref();
+            
+            for index in Self::sample_indices(rows.len(), sample_size) {
+                let (row, raw) = (&rows[index], &raw_records[index]);
+                let reversed = match &self.options.direction_column {
+                    Some(column) => match self.is_reversed_edge(row, column) {
+                        Some(reversed) => reversed,
+                        None => continue,
+                    },
+                    None => false,
+                };
+                let endpoint = |id_column: &str, label_column: &str, key: &str| {
+                    let id = row.get(id_column).filter(|id| !id.is_empty())?;
+                    let raw_label = row.get(label_column).map_or("", |l| l.trim());
+                    let label = self.label_mapping.get(raw_label).map_or(raw_label, |l| l.as_str());
+                    Some(format!("(:{} {{{}: {}}})", self.label_identifier(self.primary_label(label)), key,
+                                 Self::typed_cypher_literal(id, raw.quoted_columns.contains(id_column))))
+                };
+                // Keys belong to the CSV columns, so they stay put when the direction is reversed
+                let (source_key, target_key) = self.endpoint_keys(rel_type);
+                let (Some(source), Some(target)) = (endpoint("source", "source_label", source_key), endpoint("target", "target_label", target_key)) else {
+                    continue;
+                };
+                
+                let mut expected: Vec<(String, String, bool)> = row.iter()
+                    .filter(|(key, value)| !self.options.topology_only && !edge_key_columns.contains(&key.as_str())
+                        && weight_column != Some(key.as_str()) && !value.is_empty()
+                        && self.options.direction_column.as_ref() != Some(*key)
+                        && !Self::is_explicit_null(value, raw.quoted_columns.contains(*key), &self.options)
+                        && !append_columns.contains(key) && !self.options.vector_columns.contains_key(*key)
+                        && !Self::is_uncompared_column(key, &self.options))
+                    .map(|(key, value)| {
+                        // Same `Date:Date` -> `Date` cleanup as the edge loader
+                        let clean_key = match key.split_once(':') {
+                            Some((first, second)) if first == second => first.to_string(),
+                            _ => key.clone(),
+                        };
+                        (clean_key, value.clone(), raw.quoted_columns.contains(key))
+                    })
+                    .collect();
+                expected.sort();
+                
+                let pattern = if reversed {
+                    format!("{}<-[e:{}]-{}", source, rel_type_ident, target)
+                } else {
+                    format!("{}-[e:{}]->{}", source, rel_type_ident, target)
+                };
+                mismatches += self.verify_entity(&file_name, raw.line, &pattern, &expected).await?;
+                rows_checked += 1;
+            }
+        }
+        
+        if mismatches > 0 {
+            return Err(anyhow!("Verification found {} mismatched properties in {} sampled rows", mismatches, rows_checked));
+        }
+        info!("✅ Verified {} sampled rows: stored properties match the CSV files", rows_checked);
+        Ok(())
+    }
+    
+    /// Canonical typed form of a CSV value as the loader stores it: `i:42`, `f:1.5` or `s:text`
+    pub(crate) fn canonical_csv_value(value: &str, quoted: bool) -> String {
+        if !quoted && !Self::is_formatted_number(value) {
+            if let Ok(number) = value.parse::<i64>() {
+                return format!("i:{}", number);
+            }
+            if let Ok(number) = value.parse::<f64>() {
+                return format!("f:{}", number);
+            }
+        }
+        format!("s:{}", value)
+    }
+    
+    /// Canonical typed form of a stored value, matching `canonical_csv_value`
+    pub(crate) fn canonical_stored_value(value: &FalkorValue) -> String {
+        match value {
+            FalkorValue::I64(number) => format!("i:{}", number),
+            FalkorValue::F64(number) => format!("f:{}", number),
+            FalkorValue::String(text) => format!("s:{}", text),
+            FalkorValue::Bool(b) => format!("b:{}", b),
+            other => format!("?:{:?}", other),
+        }
+    }
+    
+    /// Whether `--verify-sample` and `--checksum` leave out a property column: `--array-column`
+    /// lists, and `--schema` columns of a type other than string or int for any label or type
+    fn is_uncompared_column(column: &str, options: &LoaderOptions) -> bool {
+        options.array_columns.contains_key(column)
+            || options.schema.values().any(|types| types.get(column).is_some_and(|t| !t.is_comparable()))
+    }
+    
+    /// Whether a node column is not a property: `id`, `labels` and the `--label-column`
+    pub(crate) fn is_node_key_column(column: &str, options: &LoaderOptions) -> bool {
+        column == "id" || column == "labels" || options.label_column.as_deref() == Some(column)
+    }
+    
+    /// Fingerprint of an entity: its canonical ids, then `key=value` for each non-null
+    /// property sorted by key, separated by the ASCII unit/record separators
+    pub(crate) fn entity_fingerprint(ids: &[String], properties: &HashMap<String, String>, string_keys: &HashSet<String>, options: &LoaderOptions) -> String {
+        let mut entries: Vec<(&String, String)> = properties.iter()
+            .filter(|(key, value)| !value.is_empty() && !Self::is_uncompared_column(key, options)
+                && !Self::is_explicit_null(value, string_keys.contains(*key), options))
+            .map(|(key, value)| {
+                let quoted = string_keys.contains(key);
+                let canonical = match Self::inferred_bool(value, quoted, options) {
+                    Some(b) => format!("b:{}", b),
+                    None => Self::canonical_csv_value(value, quoted),
+                };
+                (key, canonical)
+            })
+            .collect();
+        entries.sort();
+        Self::join_fingerprint(ids, entries.iter().map(|(key, value)| (key.as_str(), value.as_str())))
+    }
+    
+    fn join_fingerprint<'a>(ids: &[String], entries: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+        let mut fingerprint = ids.join("\u{1f}");
+        for (key, value) in entries {
+            fingerprint.push('\u{1e}');
+            fingerprint.push_str(key);
+            fingerprint.push('=');
+            fingerprint.push_str(value);
+        }
+        fingerprint
+    }
+    
+    /// Checksum of the entities matched by `pattern`, read back page by page in internal id
+    /// order. `var` is the entity variable, `id_exprs` the ids in fingerprint order; stored
+    /// properties in `skipped` are not part of the fingerprint.
+    async fn graph_checksum(&self, pattern: &str, var: &str, id_exprs: &[&str], skipped: &HashSet<&str>) -> Result<Checksum> {
+        const PAGE_SIZE: usize = 10_000;
+        let mut checksum = Checksum::default();
+        let mut last_id = -1;
+        
+        loop {
+            let query = format!(
+                "MATCH {p} WHERE id({v}) > {last} RETURN id({v}), {ids}, properties({v}) ORDER BY id({v}) LIMIT {limit}",
+                p = pattern, v = var, last = last_id, ids = id_exprs.join(", "), limit = PAGE_SIZE
+            );
+            let rows = self.execute_read_query_rows(&query).await?;
+            for row in &rows {
+                if let Some(FalkorValue::I64(internal_id)) = row.first() {
+                    last_id = *internal_id;
+                }
+                let ids: Vec<String> = row.iter().skip(1).take(id_exprs.len())
+                    .map(Self::canonical_stored_value)
+                    .collect();
+                let mut entries: Vec<(&str, String)> = match row.last() {
+                    Some(FalkorValue::Map(properties)) => properties.iter()
+                        .filter(|(key, _)| !skipped.contains(key.as_str()))
+                        .map(|(key, value)| (key.as_str(), Self::canonical_stored_value(value)))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                entries.sort();
+                checksum.add(&Self::join_fingerprint(&ids, entries.iter().map(|(key, value)| (*key, value.as_str()))));
+            }
+            if rows.len() < PAGE_SIZE {
+                return Ok(checksum);
+            }
+        }
+    }
+    
+    /// Node count per label and relationship count per type that loading the given files into
+    /// an empty graph should produce, counted from their data rows. A row labelled `A:B`
+    /// counts towards both `A` and `B`, and with `--rel-type-template` or `--rel-type-column`
+    /// each edge row counts towards the type it renders to (rows without one are left out).
+    fn expected_counts(&self, node_files: &[PathBuf], edge_files: &[PathBuf]) -> Result<(BTreeMap<String, usize>, BTreeMap<String, usize>)> {
+        let mut labels = BTreeMap::new();
+        for file_path in node_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let file_label = Self::entity_name_from_file_name(&file_name, "nodes_");
+            let column = self.labels_column();
+            let mut rdr = Self::csv_reader(file_path, self.options.delimiter.0)?;
+            let mut label_sets: BTreeMap<String, usize> = BTreeMap::new();
+            match rdr.headers()?.iter().position(|h| h == column) {
+                Some(index) => {
+                    for record in rdr.records() {
+                        let record = record?;
+                        let row = HashMap::from([(column.to_string(), record.get(index).unwrap_or("").to_string())]);
+                        if let Some(label) = self.row_label(&row, file_label) {
+                            *label_sets.entry(label).or_default() += 1;
+                        }
+                    }
+                }
+                None => {
+                    if let Some(raw_label) = file_label {
+                        label_sets.insert(self.label_name(raw_label), self.count_total_records(std::slice::from_ref(file_path))?);
+                    }
+                }
+            }
+            // A node of `A:B` is counted by both `(n:A)` and `(n:B)`
+            for (label_set, count) in label_sets {
+                for label in self.label_parts(&label_set) {
+                    *labels.entry(label.to_string()).or_default() += count;
+                }
+            }
+        }
+        
+        let mut rel_types = BTreeMap::new();
+        for file_path in edge_files {
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match self.row_rel_type_template() {
+                Some((template, option)) => {
+                    let mut rdr = Self::csv_reader(file_path, self.options.delimiter.0)?;
+                    for result in rdr.deserialize::<HashMap<String, String>>() {
+                        // Rows without a type are rejected while loading
+                        if let Ok(rel_type) = Self::render_rel_type(&template, option, &result?) {
+                            *rel_types.entry(rel_type).or_default() += 1;
+                        }
+                    }
+                }
+                None => {
+                    let rel_type = Self::entity_name_from_file_name(&file_name, "edges_").unwrap_or(&file_name);
+                    *rel_types.entry(rel_type.to_string()).or_default() += self.count_total_records(std::slice::from_ref(file_path))?;
+                }
+            }
+        }
+        Ok((labels, rel_types))
+    }
+    
+    /// `--verify`: compare the CSV row counts with the node count of every label and the
+    /// relationship count of every type. Rows the load filtered or merged make the graph
+    /// count lower, so in MERGE mode the expected counts are only upper bounds and a
+    /// mismatch is a warning.
+    pub(crate) async fn verify_counts(&self, node_files: &[PathBuf], edge_files: &[PathBuf]) -> Result<()> {
+        info!("\n🔍 Comparing CSV row counts with the graph...");
+        let (labels, rel_types) = self.expected_counts(node_files, edge_files)?;
+        
+        let mut mismatches = Vec::new();
+        info!("   {:<32} {:>12} {:>12}", "LABEL / TYPE", "EXPECTED", "ACTUAL");
+        for (name, expected, is_relationship) in labels.iter().map(|(name, count)| (name, *count, false))
+            .chain(rel_types.iter().map(|(name, count)| (name, *count, true))) {
+            let query = if is_relationship {
+                format!("MATCH ()-[r:{}]->() RETURN count(r)", self.rel_type_identifier(name))
+            } else {
+                format!("MATCH (n:{}) RETURN count(n)", self.label_identifier(name))
+            };
+            let actual = match self.execute_graph_query(&query).await?.first().and_then(|row| row.first()) {
+                Some(FalkorValue::I64(count)) => *count as usize,
+                _ => 0,
+            };
+            let display_name = if is_relationship { format!("[:{}]", name) } else { format!("(:{})", name) };
+            if expected.abs_diff(actual) > self.options.verify_tolerance {
+                mismatches.push(display_name.clone());
+                info!("   {:<32} {:>12} {:>12}  ❌", display_name, expected, actual);
+            } else {
+                info!("   {:<32} {:>12} {:>12}  ✅", display_name, expected, actual);
+            }
+        }
+        
+        if mismatches.is_empty() {
+            info!("✅ Graph counts match the CSV files");
+            return Ok(());
+        }
+        if self.merge_mode {
+            warn!("⚠️ Counts differ by more than {} for {:?} (expected counts are upper bounds in MERGE mode)",
+                  self.options.verify_tolerance, mismatches);
+            return Ok(());
+        }
+        Err(anyhow!("Counts differ by more than {} from the CSV files for {} labels/relationship types: {}",
+                    self.options.verify_tolerance, mismatches.len(), mismatches.join(", ")))
+    }
+    
+    /// Compare the checksum of the data sent for each label and relationship type with one
+    /// computed from the graph. Fails when any differ.
+    pub(crate) async fn verify_checksums(&self) -> Result<()> {
+        info!("\n🔐 Comparing checksums of the sent data with the graph...");
+        let sent = std::mem::take(&mut *self.checksums.lock().unwrap_or_else(|e| e.into_inner()));
+        
+        // Columns stored outside the props map or in forms that don't read back as written
+        // are not part of the checksum
+        let mut skipped: HashSet<&str> = self.options.vector_columns.keys().map(|c| c.as_str()).collect();
+        skipped.extend(self.options.append_list_columns.iter().map(|c| c.as_str()));
+        skipped.extend(self.options.array_columns.keys().map(|c| c.as_str()));
+        skipped.extend(self.options.schema.values().flatten()
+            .filter(|(_, column_type)| !column_type.is_comparable())
+            .map(|(column, _)| column.as_str()));
+        skipped.extend(self.options.weight_column.as_deref());
+        skipped.extend(self.options.stamp_source.as_deref());
+        skipped.extend(self.options.label_as_property.as_deref());
+        
+        let mut mismatches = 0;
+        for ((is_relationship, name), expected) in &sent {
+            let stored = if *is_relationship {
+                let pattern = format!("(a)-[r:{}]->(b)", self.rel_type_identifier(name));
+                self.graph_checksum(&pattern, "r", &["a.id", "b.id"], &skipped).await?
+            } else {
+                // The node id is already the fingerprint's id
+                let mut node_skipped = skipped.clone();
+                node_skipped.insert("id");
+                let pattern = format!("(n:{})", self.label_identifier(name));
+                self.graph_checksum(&pattern, "n", &["n.id"], &node_skipped).await?
+            };
+            
+            let kind = if *is_relationship { "relationships" } else { "nodes" };
+            if stored == *expected {
+                info!("  ✅ {} {}: checksum {:016x} over {} entities", name, kind, stored.sum, stored.count);
+            } else {
+                mismatches += 1;
+                error!("  ❌ {} {}: sent checksum {:016x} over {} rows, graph has {:016x} over {} entities",
+                       name, kind, expected.sum, expected.count, stored.sum, stored.count);
+            }
+        }
+        
+        if mismatches > 0 {
+            return Err(anyhow!("Checksum mismatch for {} labels/relationship types", mismatches));
+        }
+        Ok(())
+    }
+    
+    /// Counts per node label and per relationship type of a graph
+    async fn graph_counts(&self, graph_name: &str) -> Result<(BTreeMap<String, i64>, BTreeMap<String, i64>)> {
+        let to_counts = |rows: Vec<Vec<FalkorValue>>| -> BTreeMap<String, i64> {
+            rows.iter()
+                .filter_map(|row| match (row.first(), row.get(1)) {
+                    (Some(name), Some(FalkorValue::I64(count))) => Some((Self::falkor_value_to_string(name), *count)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let labels = self.execute_read_query_rows_on(graph_name, "MATCH (n) UNWIND labels(n) AS label RETURN label, count(*)").await?;
+        let types = self.execute_read_query_rows_on(graph_name, "MATCH ()-[r]->() RETURN type(r), count(r)").await?;
+        Ok((to_counts(labels), to_counts(types)))
+    }
+    
+    /// Differences between two count maps as `{name, count, other_count}` entries
+    fn count_differences(ours: &BTreeMap<String, i64>, theirs: &BTreeMap<String, i64>, name_key: &str) -> Vec<serde_json::Value> {
+        ours.keys().chain(theirs.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|name| {
+                let (count, other_count) = (ours.get(name).copied().unwrap_or(0), theirs.get(name).copied().unwrap_or(0));
+                (count != other_count).then(|| serde_json::json!({ name_key: name, "count": count, "other_count": other_count }))
+            })
+            .collect()
+    }
+    
+    /// Compare this graph with `other_graph` on the same server: node counts per label,
+    /// relationship counts per type and, with `sample_size` > 0, the properties of sampled
+    /// nodes looked up by id. Prints the differences as JSON and returns whether the graphs
+    /// matched.
+    pub async fn compare_graphs(&self, other_graph: &str, sample_size: usize) -> Result<bool> {
+        info!("🔍 Comparing graph '{}' with '{}'...", self.graph_name, other_graph);
+        let (labels, types) = self.graph_counts(&self.graph_name).await?;
+        let (other_labels, other_types) = self.graph_counts(other_graph).await?;
+        let label_differences = Self::count_differences(&labels, &other_labels, "label");
+        let type_differences = Self::count_differences(&types, &other_types, "type");
+        
+        let mut property_differences = Vec::new();
+        for label in labels.keys().filter(|_| sample_size > 0) {
+            let label_ident = self.quote_identifier(label.clone());
+            let sample = self.execute_read_query_rows(&format!(
+                "MATCH (n:{}) WHERE n.id IS NOT NULL RETURN n.id, properties(n) LIMIT {}", label_ident, sample_size
+            )).await?;
+            
+            for row in &sample {
+                let (Some(id), Some(FalkorValue::Map(properties))) = (row.first(), row.get(1)) else {
+                    continue;
+                };
+                let id_literal = match id {
+                    FalkorValue::String(text) => Self::string_literal(text),
+                    other => Self::falkor_value_to_string(other),
+                };
+                let other_rows = self.execute_read_query_rows_on(other_graph, &format!(
+                    "MATCH (n:{} {{id: {}}}) RETURN properties(n) LIMIT 1", label_ident, id_literal
+                )).await?;
+                let Some(FalkorValue::Map(other_properties)) = other_rows.first().and_then(|row| row.first()) else {
+                    property_differences.push(serde_json::json!({
+                        "label": label, "id": Self::falkor_value_to_string(id), "missing_in_other": true,
+                    }));
+                    continue;
+                };
+                
+                let keys: BTreeSet<&String> = properties.keys().chain(other_properties.keys()).collect();
+                for key in keys {
+                    let (value, other_value) = (properties.get(key), other_properties.get(key));
+                    let render = |value: Option<&FalkorValue>| value.map_or("null".to_string(), |v| format!("{:?}", v));
+                    if render(value) != render(other_value) {
+                        property_differences.push(serde_json::json!({
+                            "label": label,
+                            "id": Self::falkor_value_to_string(id),
+                            "property": key,
+                            "value": render(value),
+                            "other_value": render(other_value),
+                        }));
+                    }
+                }
+            }
+        }
+        
+        let identical = label_differences.is_empty() && type_differences.is_empty() && property_differences.is_empty();
+        let report = serde_json::json!({
+            "graph": self.graph_name,
+            "compared_to": other_graph,
+            "identical": identical,
+            "label_counts": label_differences,
+            "relationship_type_counts": type_differences,
+            "property_differences": property_differences,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        
+        if identical {
+            info!("✅ Graphs '{}' and '{}' match", self.graph_name, other_graph);
+        } else {
+            warn!("⚠️ Graphs '{}' and '{}' differ", self.graph_name, other_graph);
+        }
+        Ok(identical)
+    }
+    
+    /// Get statistics about the loaded graph
+    pub async fn get_graph_stats(&self) -> Result<GraphStats> {
+        // Count nodes by label
+        let node_query = "MATCH (n) RETURN labels(n) as labels, count(n) as count";
+        let nodes = self.execute_read_query_rows(node_query).await
+            .map_err(|e| anyhow!("Error getting node statistics: {}", e))?
+            .into_iter()
+            .filter_map(|row| match (row.first(), row.get(1)) {
+                (Some(FalkorValue::Array(labels)), Some(FalkorValue::I64(count))) => Some(LabelCount {
+                    labels: labels.iter().map(Self::falkor_value_to_string).collect(),
+                    count: *count,
+                }),
+                _ => None,
+            })
+            .collect();
+        
+        // Count relationships by type
+        let rel_query = "MATCH ()-[r]->() RETURN type(r) as type, count(r) as count";
+        let relationships = self.execute_read_query_rows(rel_query).await
+            .map_err(|e| anyhow!("Error getting relationship statistics: {}", e))?
+            .into_iter()
+            .filter_map(|row| match (row.first(), row.get(1)) {
+                (Some(rel_type), Some(FalkorValue::I64(count))) => Some(RelTypeCount {
+                    rel_type: Self::falkor_value_to_string(rel_type),
+                    count: *count,
+                }),
+                _ => None,
+            })
+            .collect();
+        
+        Ok(GraphStats { nodes, relationships })
+    }
+}